@@ -3,6 +3,7 @@ use std::io::Result;
 use std::process::exit;
 
 use aoc::read_lines;
+use aoc::text::{AhoCorasick, Match};
 
 fn main() {
     let args: Vec<String> = env::args().collect();
@@ -31,87 +32,30 @@ fn main() {
 }
 
 fn calculate_1(lines: impl Iterator<Item = Result<String>>) -> i32 {
+    let digits = AhoCorasick::new(&["1", "2", "3", "4", "5", "6", "7", "8", "9"]);
     lines
         .map(Result::unwrap)
-        .map(|line| value_for_line_1(&line))
+        .map(|line| value_for_line(&line, &digits))
         .sum()
 }
 
 fn calculate_2(lines: impl Iterator<Item = Result<String>>) -> i32 {
+    // pattern i and i + 9 both stand for digit i + 1
+    let digits = AhoCorasick::new(&[
+        "1", "2", "3", "4", "5", "6", "7", "8", "9",
+        "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+    ]);
     lines
         .map(Result::unwrap)
-        .map(|line| value_for_line_2(&line))
+        .map(|line| value_for_line(&line, &digits))
         .sum()
 }
 
-fn value_for_line_1(line: &str) -> i32 {
-
-    let digits = vec!["1", "2", "3", "4", "5", "6", "7", "8", "9"];
-
-    let len = line.len();
-    let (mut left, mut right): (Option<&str>, Option<&str>) = (None, None);
-
-    'outer: for i in 0..len {
-        let tail = &line[i..len]; // this only works because the input is ASCII
-        for &digit in &digits {
-            if tail.starts_with(digit) {
-                left = Some(digit);
-                break 'outer;
-            }
-        };
-    }
-
-    'outer: for i in 0..len {
-        let head = &line[0..len-i]; // this only works because the input is ASCII
-        for &digit in &digits {
-            if head.ends_with(digit) {
-                right = Some(digit);
-                break 'outer;
-            }
-        };
-    }
-
-    format!("{}{}", left.unwrap_or("0"), right.unwrap_or("0")).parse().unwrap()
-}
-
-fn value_for_line_2(line: &str) -> i32 {
-
-    let digits = vec![
-        ("1", "one"),
-        ("2", "two"),
-        ("3", "three"),
-        ("4", "four"),
-        ("5", "five"),
-        ("6", "six"),
-        ("7", "seven"),
-        ("8", "eight"),
-        ("9", "nine"),
-    ];
-
-    let len = line.len();
-    let (mut left, mut right): (Option<&str>, Option<&str>) = (None, None);
-
-    'outer: for i in 0..len {
-        let tail = &line[i..len]; // this only works because the input is ASCII
-        for &digit in &digits {
-            if tail.starts_with(digit.0) || tail.starts_with(digit.1) {
-                left = Some(digit.0);
-                break 'outer;
-            }
-        };
-    }
-
-    'outer: for i in 0..len {
-        let head = &line[0..len-i]; // this only works because the input is ASCII
-        for &digit in &digits {
-            if head.ends_with(digit.0) || head.ends_with(digit.1) {
-                right = Some(digit.0);
-                break 'outer;
-            }
-        };
-    }
-
-    format!("{}{}", left.unwrap_or("0"), right.unwrap_or("0")).parse().unwrap()
+fn value_for_line(line: &str, digits: &AhoCorasick) -> i32 {
+    // matches overlap (as in "twone"), so the last match to end is also the last match to start
+    let matches = digits.find_all(line.as_bytes());
+    let digit = |m: Option<&Match>| m.map_or(0, |m| m.pattern as i32 % 9 + 1);
+    10 * digit(matches.iter().min_by_key(|m| m.start)) + digit(matches.last())
 }
 
 #[cfg(test)]
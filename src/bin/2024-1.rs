@@ -1,8 +1,8 @@
-use std::collections::HashMap;
 use std::env;
 use std::io::Result;
 use std::process::exit;
 
+use aoc::collections::Counter;
 use aoc::read_lines;
 
 fn main() {
@@ -52,20 +52,18 @@ fn calculate_1(lines: impl Iterator<Item = Result<String>>) -> u32 {
 }
 
 fn calculate_2(lines: impl Iterator<Item = Result<String>>) -> u32 {
-    let mut counters: HashMap<u32, (u32, u32)> = HashMap::new();
+    let mut left = Counter::new();
+    let mut right = Counter::new();
     for line in lines {
         let text = line.unwrap();
         let mut parts = text.split_whitespace();
-        let x: u32 = parts.next().unwrap().parse().unwrap();
-        let y: u32 = parts.next().unwrap().parse().unwrap();
-
-        counters.entry(x).and_modify(|tup| *tup = (tup.0 + 1, tup.1)).or_insert((1, 0));
-        counters.entry(y).and_modify(|tup| *tup = (tup.0, tup.1 + 1)).or_insert((0, 1));
+        left.add(parts.next().unwrap().parse::<u32>().unwrap());
+        right.add(parts.next().unwrap().parse::<u32>().unwrap());
     }
 
     let mut score = 0;
-    for (val, (left, right)) in counters {
-        score += val * left * right;
+    for (val, count) in left.iter() {
+        score += val * count as u32 * right.get(val) as u32;
     }
 
     score
@@ -1,36 +1,11 @@
-use std::collections::HashMap;
 use std::env;
 use std::process::exit;
 
-fn calculate(input: &str, max_rank: usize) -> u64 {
+use aoc::stones::{blink, Stones};
 
-    let mut stones: HashMap<u64, u64> = HashMap::new();
-    input.split_ascii_whitespace()
-        .map(|s| s.parse::<u64>().unwrap())
-        .for_each(|x| {
-            stones.insert(x, stones.get(&x).unwrap_or(&0) + 1);
-        });
-
-    for _ in 0..max_rank {
-        let mut new_stones: HashMap<u64, u64> = HashMap::new();
-        for (stone, count) in stones.iter() {
-            let stone_str = stone.to_string();
-            let stones_after: Vec<u64> = 
-                if *stone == 0 {
-                    vec![1]
-                } else if stone_str.len() % 2 == 0 {
-                    let len = stone_str.len() / 2;
-                    vec![stone_str[..len].parse::<u64>().unwrap(), stone_str[len..].parse::<u64>().unwrap()]
-                } else {
-                    vec![stone * 2024]
-                };
-            for new_stone in stones_after {
-                new_stones.insert(new_stone, new_stones.get(&new_stone).unwrap_or(&0) + count);
-            }
-        }
-        stones = new_stones;
-    }
-    stones.values().sum()
+fn calculate(input: &str, max_rank: usize) -> usize {
+    let stones: Vec<u64> = input.split_ascii_whitespace().map(|s| s.parse().unwrap()).collect();
+    Stones::new(blink).count(&stones, max_rank)
 }
 
 fn main() {
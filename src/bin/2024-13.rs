@@ -1,7 +1,8 @@
-use std::cmp::min;
 use std::env;
 use std::process::exit;
 
+use aoc::math::linear::solve_2x2;
+
 struct Machine {
     a: (u64, u64),
     b: (u64, u64),
@@ -9,46 +10,15 @@ struct Machine {
 }
 
 impl Machine {
+    /// 3 tokens per press of A and 1 per press of B, 0 if the prize cannot be won
     fn min_tokens(&self) -> u64 {
-        for a in 0..min(self.p.0.div_ceil(self.a.0), self.p.1.div_ceil(self.a.1)) {
-            for b in 0..min(self.p.0.div_ceil(self.b.0), self.p.1.div_ceil(self.b.1)) {
-                if a * self.a.0 + b * self.b.0 == self.p.0 &&
-                   a * self.a.1 + b * self.b.1 == self.p.1 {
-                    return a * 3 + b;
-                }
-            }   
-        }
-        0
-    }
-
-    /*
-    // x steps of A and y steps of B must be P:
-    x a0 + y b0 = p1        (1)
-    x a1 + y b1 = p2        (2)
-
-    // transform (2)
-    x = (p2 - y b1) / a1    (3)
-
-    // to substitute x into (1)., proceed to find y from known numbers in (4)
-    (p2 - y b1) a0 / a1 + y b0 = p1
-    a0 p2 / a1 - y b1 a0 / a1 + y b0 = p1
-    y ( b0 - b1 a0 / a1 ) = p1 - a0 p2 / a1
-    y = (p1 - p2 a0 / a1) / (b0 - b1 a0 / a1)   (4)
-
-    // and remember (3) to find x
-    x = (p2 - y b1) / a1
-    */
-    fn min_tokens_efficient(&self) -> u64 {
-        let (p1, p2) = (self.p.0 as f64, self.p.1 as f64);
-        let (a0, a1) = (self.a.0 as f64, self.a.1 as f64);
-        let (b0, b1) = (self.b.0 as f64, self.b.1 as f64);
-        let y = (p1 - p2 * a0 / a1) / (b0 - b1 * a0 / a1);
-        let x = (p2 - y * b1) / a1;
-        if ((x - (x as u64) as f64) < 0.001 || ((x - (x as u64) as f64) > 0.999)) 
-        && ((y - (y as u64) as f64) < 0.001 || ((y - (y as u64) as f64) > 0.999)) {
-            3 * x.round() as u64 + y.round() as u64
-        } else {
-            0
+        // x presses of A and y presses of B must reach the prize in both directions:
+        //   x a0 + y b0 = p0
+        //   x a1 + y b1 = p1
+        let coefficients = [[self.a.0 as i64, self.b.0 as i64], [self.a.1 as i64, self.b.1 as i64]];
+        match solve_2x2(coefficients, [self.p.0 as i64, self.p.1 as i64]) {
+            Some([x, y]) => 3 * x + y,
+            None => 0,
         }
     }
 }
@@ -65,43 +35,17 @@ fn calculate_b(lines: impl Iterator<Item = Result<String, std::io::Error>>) -> u
         m.p.1 += 10000000000000;
         m
     })
-    .map(|m| m.min_tokens_efficient())
+    .map(|m| m.min_tokens())
     .sum()
 }
 
 fn parse_machines(lines: impl Iterator<Item = Result<String, std::io::Error>>) -> Vec<Machine> {
-    let mut machines: Vec<Machine> = Vec::new();
-    let mut a_buts: Vec<(u64, u64)> = Vec::new();
-    let mut b_buts: Vec<(u64, u64)> = Vec::new();
-    let mut prizes: Vec<(u64, u64)> = Vec::new();
-    // TODO: this can be done way nicer by interweaving 4 iterators I guess. Find out how.
-    lines.map(Result::unwrap).filter(|line| !line.is_empty()).enumerate().for_each(|(i, line)| {
-        match i % 3 {
-            0 => { // A
-                let (left, y) = line.split_once(", Y+").unwrap();
-                let (_, x) = left.split_once("X+").unwrap();
-                let parsed = (x.parse().unwrap(), y.parse().unwrap());
-                a_buts.push(parsed);
-            },
-            1 => { // B
-                let (left, y) = line.split_once(", Y+").unwrap();
-                let (_, x) = left.split_once("X+").unwrap();
-                let parsed = (x.parse().unwrap(), y.parse().unwrap());
-                b_buts.push(parsed);
-            },
-            2 => { // P
-                let (left, y) = line.split_once(", Y=").unwrap();
-                let (_, x) = left.split_once("X=").unwrap();
-                let parsed = (x.parse().unwrap(), y.parse().unwrap());
-                prizes.push(parsed);
-            },
-            _ => { panic!("This really should not happen") },
-        }
-    });
-    for i in 0..a_buts.len() {
-        machines.push(Machine { a: a_buts[i], b: b_buts[i], p: prizes[i] });
-    }
-    machines
+    let lines: Vec<String> = lines.map(Result::unwrap).filter(|line| !line.is_empty()).collect();
+    lines.chunks(3).map(|machine| Machine {
+        a: aoc::scan!("Button A: X+{}, Y+{}", &machine[0] => u64, u64).unwrap(),
+        b: aoc::scan!("Button B: X+{}, Y+{}", &machine[1] => u64, u64).unwrap(),
+        p: aoc::scan!("Prize: X={}, Y={}", &machine[2] => u64, u64).unwrap(),
+    }).collect()
 }
 
 fn main() {
@@ -159,6 +103,7 @@ mod tests {
             Prize: X=18641, Y=10279
         ";
         assert_eq!(calculate_a(io_lines_from(input)), 480);
+        assert_eq!(calculate_b(io_lines_from(input)), 875318608908);
         Ok(())
     }
 }
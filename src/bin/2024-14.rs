@@ -1,47 +1,24 @@
 use std::env;
 use std::process::exit;
 
-#[derive(Debug)]
-struct Robot((i32, i32), (i32, i32));
+use aoc::swarm::{Robot, Swarm};
 
-impl Robot {
-    fn new(input: &str) -> Self {
-        let (p, v) = input.split_once(" ").unwrap();
-        let comma_p = p.find(",").unwrap();
-        let comma_v = v.find(",").unwrap();
-
-        Robot((
-                p[2..comma_p].parse().unwrap(),
-                p[comma_p+1..].parse().unwrap(),
-            ),(
-                v[2..comma_v].parse().unwrap(),
-                v[comma_v+1..].parse().unwrap(),
-            ))
-    }
+fn parse_robot(input: &str) -> Robot {
+    let (px, py, vx, vy) = aoc::scan!("p={},{} v={},{}", input => i64, i64, i64, i64).unwrap();
+    Robot { position: (px, py), velocity: (vx, vy) }
 }
 
-fn calculate_a(lines: impl Iterator<Item = Result<String, std::io::Error>>, map_size: (i32, i32)) -> usize {
-    let mut robots: Vec<Robot> = lines.map(Result::unwrap).map(|s| Robot::new(&s)).collect();
-    for s in 0..100 {
-        robots = step(robots, map_size);
-    }
-    
-    let x: i32 = (map_size.0 - 1) / 2;
-    let y: i32 = (map_size.1 - 1) / 2;
+fn parse_swarm(lines: impl Iterator<Item = Result<String, std::io::Error>>, map_size: (i64, i64)) -> Swarm {
+    Swarm::new(lines.map(Result::unwrap).map(|s| parse_robot(&s)).collect(), map_size)
+}
 
-    robots.iter().filter(|r| r.0.0 < x && r.0.1 < y).count() *
-    robots.iter().filter(|r| r.0.0 > x && r.0.1 < y).count() *
-    robots.iter().filter(|r| r.0.0 < x && r.0.1 > y).count() *
-    robots.iter().filter(|r| r.0.0 > x && r.0.1 > y).count()
+fn calculate_a(lines: impl Iterator<Item = Result<String, std::io::Error>>, map_size: (i64, i64)) -> usize {
+    parse_swarm(lines, map_size).safety_factor(100)
 }
 
-fn step(robots: Vec<Robot>, map_size: (i32, i32)) -> Vec<Robot> {
-    robots.iter().map(|r| {
-        Robot ((
-            (r.0.0 + r.1.0).rem_euclid(map_size.0),
-            (r.0.1 + r.1.1).rem_euclid(map_size.1),
-        ), r.1)
-    }).collect()
+fn calculate_b(lines: impl Iterator<Item = Result<String, std::io::Error>>, map_size: (i64, i64)) -> Option<(i64, String)> {
+    let swarm = parse_swarm(lines, map_size);
+    swarm.find_picture().map(|t| (t, swarm.render_at(t)))
 }
 
 fn main() {
@@ -59,6 +36,21 @@ fn main() {
             exit(1);
         }
     }
+
+    match aoc::read_lines(file_path) {
+        Ok(lines) => { 
+            let start = std::time::Instant::now();
+            match calculate_b(lines, (101, 103)) {
+                Some((t, picture)) => println!("Answer B: {}\n{}", t, picture),
+                None => println!("Answer B: no picture found"),
+            }
+            println!("Time elapsed in expensive_function() is: {:?}", start.elapsed());
+        }
+        Err(e) => {
+            eprintln!("Problem reading file {}: {}", file_path, e);
+            exit(1);
+        }
+    }
 }
 
 #[cfg(test)]
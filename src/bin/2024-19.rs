@@ -1,28 +1,12 @@
-use std::collections::HashMap;
-use std::{env, usize};
+use std::env;
 use std::process::exit;
 
-struct RemaindersWithCounts<'a>(HashMap<&'a str, usize>); // TODO: generalize to CountedSet<Item>
-impl <'a>RemaindersWithCounts<'a> {
-    fn new() -> Self {
-        Self(HashMap::new())
-    }
-    fn new_with_pattern(pattern: &'a str) -> Self {
-        let mut value = Self::new();
-        value.insert((pattern, 1));
-        value
-    }
-    fn insert(&mut self, (remainder, count): (&'a str, usize)) {
-        match self.0.get(&remainder) {
-            None => self.0.insert(remainder, count),
-            Some(old_count) => self.0.insert(remainder, old_count + count),
-        };
-    }
-}
+use aoc::text::AhoCorasick;
 
 fn calculate(lines: impl Iterator<Item = Result<String, std::io::Error>>) -> (usize, usize) {
     let lines: Vec<_> = lines.map(Result::unwrap).collect();
     let (towels, patterns) = parse_lines(&lines);
+    let towels = AhoCorasick::new(&towels);
 
     patterns.into_iter().map(|s| {
         match number_of_solutions(&towels, s) {
@@ -32,34 +16,22 @@ fn calculate(lines: impl Iterator<Item = Result<String, std::io::Error>>) -> (us
     }).fold((0, 0), |agg, other| (agg.0 + other.0, agg.1 + other.1))
 }
 
-fn parse_lines<'a>(lines: &'a[String]) -> (Vec<&'a str>, Vec<&'a str>) {
-    let towels = lines.get(0).unwrap().split(", ").collect();
+fn parse_lines(lines: &[String]) -> (Vec<&str>, Vec<&str>) {
+    let towels = lines.first().unwrap().split(", ").collect();
     let patterns = lines[1..].iter().filter(|s| !s.is_empty()).map(String::as_str).collect();
     (towels, patterns)
 }
 
-fn number_of_solutions(towels: &[&str], pattern: &str) -> usize {
-    let mut remainders_with_count = RemaindersWithCounts::new_with_pattern(pattern);
-    while remainders_with_count.0.keys().any(|rem| !rem.is_empty()) {
-        let mut tmp = RemaindersWithCounts::new();
-        for remainder in remainders_with_count.0 {
-            // keep count of the valid answers, because all other remainders without a match are thrown away
-            if remainder.0.is_empty() {                 
-                tmp.insert(remainder);
-            }
-            for &towel in towels {
-                if remainder.0.starts_with(towel) {
-                    tmp.insert((&remainder.0[towel.len()..], remainder.1));
-                }
-            }
-        }
-        if tmp.0.is_empty() {
-            return 0;
-        } else {
-            remainders_with_count = tmp;
-        }
+fn number_of_solutions(towels: &AhoCorasick, pattern: &str) -> usize {
+    // ways[i] is the number of ways to make the pattern from index i onward: the sum over all towels matching at i
+    // of the ways to make what is left after that towel
+    let towels_by_start = towels.matches_by_start(pattern.as_bytes());
+    let mut ways = vec![0; pattern.len() + 1];
+    ways[pattern.len()] = 1;
+    for i in (0..pattern.len()).rev() {
+        ways[i] = towels_by_start[i].iter().map(|&towel| ways[i + towels.pattern_len(towel)]).sum();
     }
-    *remainders_with_count.0.get("").unwrap()
+    ways[0]
 }
 
 fn main() {
@@ -1,5 +1,7 @@
 use std::{env, process::exit};
 use aoc::read_string;
+use aoc::text::scanner::Instruction;
+use aoc::text::Scanner;
 
 fn main() {
     let args: Vec<String> = env::args().collect();
@@ -8,7 +10,7 @@ fn main() {
 
     match read_string(file_path) {
         Ok(input) => { 
-            println!("Answer A: {}", calculate_a(input));
+            println!("Answer A: {}", calculate_a(&input));
         }
         Err(e) => {
             eprintln!("Problem reading file {}: {}", file_path, e);
@@ -18,7 +20,7 @@ fn main() {
 
     match read_string(file_path) {
         Ok(input) => { 
-            println!("Answer B: {}", calculate_b(input));
+            println!("Answer B: {}", calculate_b(&input));
         }
         Err(e) => {
             eprintln!("Problem reading file {}: {}", file_path, e);
@@ -27,58 +29,27 @@ fn main() {
     }
 }
 
-fn calculate_a(input: String) -> usize {
-    input
-        .split("mul(") // note we ignore a start with (x,y), because the data is not this edge case
-        .map(value_for_str_after_mul_open)
+fn calculate_a(input: &str) -> usize {
+    Scanner::new(input)
+        .map(|token| match token.instruction {
+            Instruction::Mul(x, y) => (x * y) as usize,
+            _ => 0,
+        })
         .sum()
 }
 
-fn calculate_b(input: String) -> usize {
-    DoInstructionsSensitive(input)
-        .collect::<Vec<_>>().iter()// TODO: clearly need to change the iteration Item to &str, but need to figure that out
-        .flat_map(|do_part| do_part.split("mul(")) // again a start with (x,y) is ignored, because the data is not this edge case
-        .map(value_for_str_after_mul_open)
-        .sum()
-}
-
-fn value_for_str_after_mul_open(x: &str) -> usize {
-    match x.split_once(",") {
-        None => 0,
-        Some((x1, x2)) => {
-            x1.parse().unwrap_or(0) *
-            match x2.split_once(")") {
-                None => 0,
-                Some((x21, _)) => {
-                    x21.parse().unwrap_or(0)
-                },
-            }
-        },
-    }
-}
-
-struct DoInstructionsSensitive (String);
-
-impl Iterator for DoInstructionsSensitive {
-    type Item = String;
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.0.is_empty() {
-            return None;
+fn calculate_b(input: &str) -> usize {
+    let mut enabled = true;
+    let mut sum = 0;
+    for token in Scanner::new(input) {
+        match token.instruction {
+            Instruction::Mul(x, y) if enabled => sum += (x * y) as usize,
+            Instruction::Mul(_, _) => {},
+            Instruction::Do => enabled = true,
+            Instruction::Dont => enabled = false,
         }
-        let (next, new_self) = match self.0.split_once("don't()") {
-            None => {
-                (Some(self.0.clone()), String::from(""))
-            },
-            Some((pos, neg)) => {
-                match neg.split_once("do()") {
-                    None => { (Some(String::from(pos)), String::from("")) },
-                    Some((_, poss)) => { (Some(String::from(pos)), String::from(poss)) },
-                }
-            },
-        };
-        self.0 = new_self;
-        next
     }
+    sum
 }
 
 #[cfg(test)]
@@ -87,14 +58,14 @@ mod tests {
 
     #[test]
     fn test_a() -> std::io::Result<()> {
-        let input = String::from("xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))");
+        let input = "xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))";
         assert_eq!(calculate_a(input), 161);
         Ok(())
     }
 
     #[test]
     fn test_b() -> std::io::Result<()> {
-        let input = String::from("xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))");
+        let input = "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
         assert_eq!(calculate_b(input), 48);
         Ok(())
     }
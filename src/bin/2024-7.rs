@@ -1,52 +1,20 @@
-use std::collections::HashSet;
 use std::env;
 use std::process::exit;
 
+use aoc::calibration::{solve_preferring, Op};
+use aoc::parse::{int, literal, parse_all, separated, terminated};
 use aoc::read_lines;
 
-struct Cal {
-    to_process: Vec<usize>,
-}
-
-impl Cal {
-    pub fn new(input: &str) -> Self {
-        Cal { 
-            to_process: input.split_ascii_whitespace().map(|x| x.parse().unwrap()).collect(),
-        }
-    }
-    pub fn process(&mut self, allow_concat: bool) -> HashSet<usize> {
-        let mut processed = HashSet::new();
-        for &n in &self.to_process {
-            if processed.is_empty() {
-                processed.insert(n);
-            } else {
-                let mut inner = HashSet::new();
-                for x in processed {
-                    inner.insert(x + n);
-                    inner.insert(x * n);
-                    if allow_concat {
-                        inner.insert((x.to_string() + &n.to_string()).parse().unwrap());
-                    }
-                }
-                processed = inner;
-            }
-        }
-        processed
-    }
-}
-
-fn calculate(lines: impl Iterator<Item = std::io::Result<String>>) -> (usize, usize) {
+fn calculate(lines: impl Iterator<Item = std::io::Result<String>>) -> (u64, u64) {
+    let equation = (terminated(int(), literal(": ")), separated(int(), literal(" ")));
     lines.map(Result::unwrap).map(|line| {
-        if let Some((output, inputs)) = line.split_once(":") {
-            let mut cal = Cal::new(inputs);
-            let output = output.parse().unwrap();
-            return match (cal.process(false).contains(&output), cal.process(true).contains(&output)) {
-                (true, true) => (output, output),
-                (false, true) => (0, output),
-                _ => (0, 0),
-            }
-        } 
-        (0, 0)
+        let (output, operands): (u64, Vec<u64>) = parse_all(&equation, &line).unwrap();
+        // a witness only uses || if + and * alone cannot make the equation hold
+        match solve_preferring(output, &operands, &Op::ALL, Op::ADD_MUL.len()) {
+            Some(witness) if witness.operators.contains(&Op::Concat) => (0, output),
+            Some(_) => (output, output),
+            None => (0, 0),
+        }
     }).fold((0, 0), |x, y| (x.0 + y.0, x.1 + y.1))
 }
 
@@ -32,10 +32,7 @@ impl Roof {
                 if c == '.' {
                     continue;
                 }
-                if !antennas.contains_key(&c) {
-                    antennas.insert(c, HashSet::new());
-                }
-                antennas.get_mut(&c).unwrap().insert(Coord{x, y});
+                antennas.entry(c).or_default().insert(Coord{x, y});
             }
         }
 
@@ -1,66 +1,11 @@
 use std::env;
 use std::process::exit;
 
-fn calculate(file_map: &str) -> (usize, usize) {
-    let mut blocks = decrypt(file_map);
-
-    loop {
-        match first_space_index(&blocks) {
-            None => break,
-            Some(x) => {
-                if x == blocks.len() - 1 { // this open space is the last element
-                    break;
-                }
-                blocks[x] = blocks.pop().unwrap();
-            },
-        }
-    }
-
-    let answer_a = blocks.iter().enumerate().filter(|(_, &id)| id.is_some()).map(|(i, id)| i * id.unwrap()).sum();
+use aoc::disk::DiskMap;
 
-    (answer_a, 0)
-}
-
-fn decrypt(file_map: &str) -> Vec<Option<usize>> {
-    let mut blocks = Vec::new();
-    let mut empty_spots = Vec::new();
-    let mut id = 0;
-    for (i, c) in file_map.chars().enumerate() {
-        let is_file_block = (i % 2) == 0;
-        let digit = c.to_digit(10).unwrap();
-        for _ in 0..digit {
-            if is_file_block {
-                blocks.push(Some(id));
-            } else {
-                blocks.push(None);
-                empty_spots.push(blocks.len() - 1);
-            }
-        }
-        if is_file_block {
-            id += 1;
-        }
-    }
-    blocks
-}
-
-fn first_space_index(blocks: &[Option<usize>]) -> Option<usize> {
-    for (i, &block) in blocks.iter().enumerate() {
-        if block.is_none() {
-            return Some(i);
-        }
-    }
-    None
-}
-
-#[allow(dead_code)]
-fn print(blocks: &Vec<Option<usize>>) {
-    for i in blocks {
-        match i {
-            None => print!("."),
-            Some(x) => print!("{}", x),
-        }
-    }
-    println!();
+fn calculate(file_map: &str) -> (usize, usize) {
+    let disk = DiskMap::parse(file_map).unwrap();
+    (disk.compact_blocks().checksum(), disk.compact_files().checksum())
 }
 
 fn main() {
@@ -92,7 +37,7 @@ mod tests {
 
     #[test]
     fn test() -> std::io::Result<()> {
-        assert_eq!(calculate(&INPUT), (1928, 0));
+        assert_eq!(calculate(INPUT), (1928, 2858));
         Ok(())
     }
 }
@@ -0,0 +1,221 @@
+use std::fmt;
+
+/// A binary operator for equations that are evaluated strictly left to right
+pub trait Operator {
+    /// `left op right`, None on overflow
+    fn apply(&self, left: u64, right: u64) -> Option<u64>;
+    /// The `left` for which `left op right` is `result`, None if there is none
+    fn undo(&self, result: u64, right: u64) -> Option<u64>;
+    fn symbol(&self) -> &'static str;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Op {
+    Add,
+    Mul,
+    /// Concatenation of the digits, `12 || 345` is `12345`
+    Concat,
+}
+
+impl Op {
+    pub const ADD_MUL: [Op; 2] = [Op::Add, Op::Mul];
+    pub const ALL: [Op; 3] = [Op::Add, Op::Mul, Op::Concat];
+}
+
+/// The power of 10 just above `n`, by which a left operand is shifted when `n` is concatenated to it
+fn concat_shift(n: u64) -> Option<u64> {
+    10u64.checked_pow(n.checked_ilog10().unwrap_or(0) + 1)
+}
+
+impl Operator for Op {
+    fn apply(&self, left: u64, right: u64) -> Option<u64> {
+        match self {
+            Op::Add => left.checked_add(right),
+            Op::Mul => left.checked_mul(right),
+            Op::Concat => left.checked_mul(concat_shift(right)?)?.checked_add(right),
+        }
+    }
+
+    /// Multiplication by 0 cannot be undone (any left would do), so operands are expected to be positive
+    fn undo(&self, result: u64, right: u64) -> Option<u64> {
+        match self {
+            Op::Add => result.checked_sub(right),
+            Op::Mul => (right != 0 && result.is_multiple_of(right)).then(|| result / right),
+            Op::Concat => {
+                let shift = concat_shift(right).unwrap_or(u64::MAX); // u64::MAX would not fit the result anyway
+                (result % shift == right).then(|| result / shift)
+            },
+        }
+    }
+
+    fn symbol(&self) -> &'static str {
+        match self {
+            Op::Add => "+",
+            Op::Mul => "*",
+            Op::Concat => "||",
+        }
+    }
+}
+
+/// Operands with the operators between them that evaluate to the target
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Witness<'a, O> {
+    pub operands: &'a [u64],
+    pub operators: Vec<O>,
+}
+
+impl <O: Operator> Witness<'_, O> {
+    /// The value of the expression, evaluated left to right
+    pub fn evaluate(&self) -> Option<u64> {
+        let (first, rest) = self.operands.split_first()?;
+        rest.iter().zip(&self.operators).try_fold(*first, |left, (right, op)| op.apply(left, *right))
+    }
+}
+
+/// E.g. `81 + 40 * 27`
+impl <O: Operator> fmt::Display for Witness<'_, O> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, operand) in self.operands.iter().enumerate() {
+            if i > 0 {
+                write!(f, " {} ", self.operators[i - 1].symbol())?;
+            }
+            write!(f, "{}", operand)?;
+        }
+        Ok(())
+    }
+}
+
+/// Finds operators from `operators` to put between the `operands` such that evaluating left to right gives `target`.
+/// Works backward from the target: the last operator must be undoable with the last operand, which prunes most
+/// branches early (e.g. undoing a multiplication needs divisibility, undoing a concatenation a matching suffix).
+pub fn solve<'a, O>(target: u64, operands: &'a [u64], operators: &[O]) -> Option<Witness<'a, O>> where
+    O: Operator + Clone,
+{
+    let mut chosen = Vec::with_capacity(operands.len().saturating_sub(1));
+    if solve_backward(target, operands, operators, &mut chosen) {
+        chosen.reverse();
+        Some(Witness { operands, operators: chosen })
+    } else {
+        None
+    }
+}
+
+/// Like `solve`, but gives a witness using only the first `preferred` operators if there is one, and any witness
+/// otherwise. A single search: once some witness is found, branches through other operators are no longer followed.
+/// E.g. with `Op::ALL` and `preferred` 2, a witness containing `Op::Concat` means + and * alone cannot make it.
+pub fn solve_preferring<'a, O>(target: u64, operands: &'a [u64], operators: &[O], preferred: usize) -> Option<Witness<'a, O>> where
+    O: Operator + Clone,
+{
+    let mut chosen = Vec::with_capacity(operands.len().saturating_sub(1));
+    let mut fallback = None;
+    let found = solve_backward_preferring(target, operands, operators, preferred, false, &mut chosen, &mut fallback);
+    let mut chosen = if found { chosen } else { fallback? };
+    chosen.reverse();
+    Some(Witness { operands, operators: chosen })
+}
+
+// Returns whether `chosen` holds a witness of preferred operators only. Otherwise `fallback` holds the first witness
+// found that does use another operator, if any.
+fn solve_backward_preferring<O>(target: u64, operands: &[u64], operators: &[O], preferred: usize, uses_other: bool,
+    chosen: &mut Vec<O>, fallback: &mut Option<Vec<O>>) -> bool where
+    O: Operator + Clone,
+{
+    if uses_other && fallback.is_some() {
+        return false; // nothing to gain below here
+    }
+    match operands {
+        [] => false,
+        [first] => {
+            if *first == target && uses_other {
+                *fallback = Some(chosen.clone());
+            }
+            *first == target && !uses_other
+        },
+        [rest @ .., last] => operators.iter().enumerate().any(|(i, op)| {
+            let Some(left) = op.undo(target, *last) else { return false };
+            chosen.push(op.clone());
+            let found = solve_backward_preferring(left, rest, operators, preferred, uses_other || i >= preferred, chosen, fallback);
+            if !found {
+                chosen.pop();
+            }
+            found
+        }),
+    }
+}
+
+// `chosen` collects the operators from last to first
+fn solve_backward<O>(target: u64, operands: &[u64], operators: &[O], chosen: &mut Vec<O>) -> bool where
+    O: Operator + Clone,
+{
+    match operands {
+        [] => false,
+        [first] => *first == target,
+        [rest @ .., last] => operators.iter().any(|op| {
+            let Some(left) = op.undo(target, *last) else { return false };
+            chosen.push(op.clone());
+            let found = solve_backward(left, rest, operators, chosen);
+            if !found {
+                chosen.pop();
+            }
+            found
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solve() {
+        let witness = solve(3267, &[81, 40, 27], &Op::ADD_MUL).unwrap();
+        assert_eq!(witness.evaluate(), Some(3267));
+        assert_eq!(solve(190, &[10, 19], &Op::ADD_MUL).unwrap().to_string(), "10 * 19");
+        assert_eq!(solve(7290, &[6, 8, 6, 15], &Op::ADD_MUL), None);
+        assert_eq!(solve(7290, &[6, 8, 6, 15], &Op::ALL).unwrap().to_string(), "6 * 8 || 6 * 15");
+        assert_eq!(solve(156, &[15, 6], &Op::ALL).unwrap().operators, vec![Op::Concat]);
+        assert_eq!(solve(5, &[5], &Op::ALL).unwrap().operators, vec![]);
+        assert_eq!(solve(5, &[], &Op::ALL), None);
+    }
+
+    #[test]
+    fn test_concat() {
+        assert_eq!(Op::Concat.apply(12, 345), Some(12345));
+        assert_eq!(Op::Concat.apply(12, 0), Some(120));
+        assert_eq!(Op::Concat.undo(12345, 345), Some(12));
+        assert_eq!(Op::Concat.undo(12345, 45), Some(123));
+        assert_eq!(Op::Concat.undo(12345, 44), None);
+        assert_eq!(Op::Concat.apply(u64::MAX / 10, 99), None);
+    }
+
+    #[test]
+    fn test_solve_preferring() {
+        let preferring = |target, operands: &[u64]| {
+            solve_preferring(target, operands, &Op::ALL, 2).map(|witness| witness.to_string())
+        };
+        assert_eq!(preferring(7290, &[6, 8, 6, 15]), Some(String::from("6 * 8 || 6 * 15")));
+        assert_eq!(preferring(3267, &[81, 40, 27]), Some(String::from("81 * 40 + 27")));
+        // the search finds 1 || 2 + 6 first, but keeps looking for a witness without ||
+        assert_eq!(solve(18, &[1, 2, 6], &Op::ALL).unwrap().to_string(), "1 || 2 + 6");
+        assert_eq!(preferring(18, &[1, 2, 6]), Some(String::from("1 + 2 * 6")));
+        assert_eq!(solve_preferring(18, &[1, 2, 6], &Op::ALL, 0).unwrap().to_string(), "1 || 2 + 6");
+        assert_eq!(preferring(83, &[17, 5]), None);
+    }
+
+    // Subtraction, to show other operator sets plug in
+    struct Sub;
+    impl Operator for Sub {
+        fn apply(&self, left: u64, right: u64) -> Option<u64> { left.checked_sub(right) }
+        fn undo(&self, result: u64, right: u64) -> Option<u64> { result.checked_add(right) }
+        fn symbol(&self) -> &'static str { "-" }
+    }
+    impl Clone for Sub {
+        fn clone(&self) -> Self { Sub }
+    }
+
+    #[test]
+    fn test_custom_operator() {
+        assert_eq!(solve(3, &[10, 4, 3], &[Sub]).unwrap().to_string(), "10 - 4 - 3");
+        assert!(solve(4, &[10, 4, 3], &[Sub]).is_none());
+    }
+}
@@ -0,0 +1,149 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// A multiset: items with the number of times they were added.
+/// Items also remember when they were first added, which orders items with equal counts.
+#[derive(Debug, Clone)]
+pub struct Counter<T: Hash + Eq>(HashMap<T, Count>);
+
+#[derive(Debug, Clone, Copy)]
+struct Count {
+    n: usize,
+    /// The number of distinct items before this one was first added
+    first: usize,
+}
+
+impl <T: Hash + Eq> Default for Counter<T> {
+    fn default() -> Self {
+        Counter(HashMap::new())
+    }
+}
+
+/// Equal if the counts are, regardless of the order the items were added in
+impl <T: Hash + Eq> PartialEq for Counter<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().all(|(item, n)| other.get(item) == n)
+    }
+}
+
+impl <T: Hash + Eq> Eq for Counter<T> {}
+
+impl <T: Hash + Eq> Counter<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add(&mut self, item: T) {
+        self.add_n(item, 1);
+    }
+
+    /// Adds `item` `n` times, adding 0 times keeps the counter unchanged
+    pub fn add_n(&mut self, item: T, n: usize) {
+        if n > 0 {
+            let first = self.0.len();
+            self.0.entry(item).or_insert(Count { n: 0, first }).n += n;
+        }
+    }
+
+    /// The count of `item`, 0 if it was never added
+    pub fn get(&self, item: &T) -> usize {
+        self.0.get(item).map_or(0, |count| count.n)
+    }
+
+    /// Adds all items of `other` with their counts, new items in the order they were added to `other`
+    pub fn merge(&mut self, other: Counter<T>) {
+        for (item, n) in other {
+            self.add_n(item, n);
+        }
+    }
+
+    /// The number of distinct items
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// The sum of all counts
+    pub fn total(&self) -> usize {
+        self.0.values().map(|count| count.n).sum()
+    }
+
+    /// (item, count) pairs in arbitrary order
+    pub fn iter(&self) -> impl Iterator<Item = (&T, usize)> {
+        self.0.iter().map(|(item, count)| (item, count.n))
+    }
+
+    /// (item, count) pairs from the highest count to the lowest, equal counts in the order the items were first added
+    pub fn iter_by_count(&self) -> impl Iterator<Item = (&T, usize)> {
+        let mut pairs: Vec<_> = self.0.iter().collect();
+        pairs.sort_by_key(|(_, count)| (std::cmp::Reverse(count.n), count.first));
+        pairs.into_iter().map(|(item, count)| (item, count.n))
+    }
+
+    /// The `n` items with the highest counts
+    pub fn most_common(&self, n: usize) -> Vec<(&T, usize)> {
+        self.iter_by_count().take(n).collect()
+    }
+}
+
+impl <T: Hash + Eq> FromIterator<T> for Counter<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut counter = Counter::new();
+        counter.extend(iter);
+        counter
+    }
+}
+
+impl <T: Hash + Eq> Extend<T> for Counter<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|item| self.add(item));
+    }
+}
+
+impl <T: Hash + Eq> IntoIterator for Counter<T> {
+    type Item = (T, usize);
+    type IntoIter = std::vec::IntoIter<(T, usize)>;
+
+    /// (item, count) pairs in the order the items were first added
+    fn into_iter(self) -> Self::IntoIter {
+        let mut items: Vec<_> = self.0.into_iter().collect();
+        items.sort_by_key(|(_, count)| count.first);
+        items.into_iter().map(|(item, count)| (item, count.n)).collect::<Vec<_>>().into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_counter() {
+        let mut counter: Counter<char> = "abracadabra".chars().collect();
+        assert_eq!((counter.get(&'a'), counter.get(&'z'), counter.len(), counter.total()), (5, 0, 5, 11));
+        assert_eq!(counter.most_common(1), vec![(&'a', 5)]);
+        assert_eq!(counter.most_common(3), vec![(&'a', 5), (&'b', 2), (&'r', 2)]); // b before r, as it came first
+
+        counter.add_n('z', 0);
+        assert_eq!(counter.len(), 5);
+        counter.merge(Counter::from_iter(['z', 'z', 'a']));
+        assert_eq!((counter.get(&'a'), counter.get(&'z'), counter.total()), (6, 2, 14));
+
+        let items: Vec<(char, usize)> = counter.iter_by_count().map(|(&c, n)| (c, n)).collect();
+        assert_eq!(items, vec![('a', 6), ('b', 2), ('r', 2), ('z', 2), ('c', 1), ('d', 1)]);
+        for _ in 0..20 {
+            let counter: Counter<u32> = (0..100).rev().collect();
+            assert_eq!(counter.most_common(3), vec![(&99, 1), (&98, 1), (&97, 1)]);
+        }
+    }
+
+    #[test]
+    fn test_counter_equality_ignores_order() {
+        let ab: Counter<char> = "aab".chars().collect();
+        assert_eq!(ab, "baa".chars().collect());
+        assert_ne!(ab, "abb".chars().collect());
+        assert_eq!("baa".chars().collect::<Counter<char>>().into_iter().collect::<Vec<_>>(), vec![('b', 1), ('a', 2)]);
+    }
+}
@@ -0,0 +1,172 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::fmt;
+
+/// A run of consecutive blocks belonging to one file, a file may consist of several extents after block compaction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Extent {
+    pub id: usize,
+    pub start: usize,
+    pub len: usize,
+}
+
+/// The layout of files on a disk of `size` blocks, the blocks not covered by an extent are free
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiskMap {
+    extents: Vec<Extent>,
+    size: usize,
+}
+
+impl DiskMap {
+    /// Parses the dense format where digits alternate between the length of a file and the length of the free space
+    /// after it, e.g. `12345`. Files get ids in order of appearance. None if the input contains a non-digit.
+    pub fn parse(input: &str) -> Option<Self> {
+        let mut extents = Vec::new();
+        let mut start = 0;
+        for (i, c) in input.trim().chars().enumerate() {
+            let len = c.to_digit(10)? as usize;
+            if i % 2 == 0 && len > 0 {
+                extents.push(Extent { id: i / 2, start, len });
+            }
+            start += len;
+        }
+        Some(DiskMap { extents, size: start })
+    }
+
+    /// The extents ordered by position on disk
+    pub fn extents(&self) -> &[Extent] {
+        &self.extents
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// The file id per block, None for a free block
+    pub fn blocks(&self) -> Vec<Option<usize>> {
+        let mut blocks = vec![None; self.size];
+        for extent in &self.extents {
+            blocks[extent.start..extent.start + extent.len].fill(Some(extent.id));
+        }
+        blocks
+    }
+
+    fn from_blocks(blocks: &[Option<usize>]) -> Self {
+        let mut extents: Vec<Extent> = Vec::new();
+        for (i, block) in blocks.iter().enumerate() {
+            let Some(id) = *block else { continue };
+            match extents.last_mut() {
+                Some(last) if last.id == id && last.start + last.len == i => last.len += 1,
+                _ => extents.push(Extent { id, start: i, len: 1 }),
+            }
+        }
+        DiskMap { extents, size: blocks.len() }
+    }
+
+    /// Moves blocks one at a time from the end of the disk to the leftmost free block, until there are no gaps
+    pub fn compact_blocks(&self) -> Self {
+        let mut blocks = self.blocks();
+        let (mut free, mut last) = (0, blocks.len());
+        loop {
+            while free < last && blocks[free].is_some() {
+                free += 1;
+            }
+            while last > free && blocks[last - 1].is_none() {
+                last -= 1;
+            }
+            if last - free < 2 {
+                break;
+            }
+            blocks.swap(free, last - 1);
+        }
+        DiskMap::from_blocks(&blocks)
+    }
+
+    /// Tries to move each file once, in order of decreasing id, to the leftmost free span that fits the whole file.
+    /// Keeps a min-heap of span starts per span length, so finding the span is a look at the top of a few heaps.
+    /// Spans freed by moving a file are never used again, as the files still to move are all to their left.
+    pub fn compact_files(&self) -> Self {
+        let mut free_by_len: Vec<BinaryHeap<Reverse<usize>>> = Vec::new();
+        let mut end = 0;
+        for extent in &self.extents {
+            add_span(&mut free_by_len, end, extent.start - end);
+            end = extent.start + extent.len;
+        }
+
+        let mut extents = self.extents.clone();
+        extents.sort_by_key(|extent| Reverse(extent.id));
+        for extent in extents.iter_mut() {
+            let span = free_by_len.iter().enumerate().skip(extent.len)
+                .filter_map(|(len, heap)| heap.peek().map(|&Reverse(start)| (start, len)))
+                .filter(|&(start, _)| start < extent.start)
+                .min();
+            if let Some((start, len)) = span {
+                free_by_len[len].pop();
+                add_span(&mut free_by_len, start + extent.len, len - extent.len);
+                extent.start = start;
+            }
+        }
+        extents.sort_by_key(|extent| extent.start);
+        DiskMap { extents, size: self.size }
+    }
+
+    /// The sum over all used blocks of position times file id
+    pub fn checksum(&self) -> usize {
+        self.extents.iter()
+            .map(|extent| extent.id * (extent.start..extent.start + extent.len).sum::<usize>())
+            .sum()
+    }
+}
+
+fn add_span(free_by_len: &mut Vec<BinaryHeap<Reverse<usize>>>, start: usize, len: usize) {
+    if len == 0 {
+        return;
+    }
+    if free_by_len.len() <= len {
+        free_by_len.resize_with(len + 1, BinaryHeap::new);
+    }
+    free_by_len[len].push(Reverse(start));
+}
+
+/// Renders the blocks as in the puzzle statement, e.g. `0..111....22222`
+impl fmt::Display for DiskMap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for block in self.blocks() {
+            match block {
+                None => write!(f, ".")?,
+                Some(id) => write!(f, "{}", id)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "2333133121414131402";
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(DiskMap::parse("12345").unwrap().to_string(), "0..111....22222");
+        assert_eq!(DiskMap::parse(INPUT).unwrap().to_string(), "00...111...2...333.44.5555.6666.777.888899");
+        assert_eq!(DiskMap::parse("12a"), None);
+    }
+
+    #[test]
+    fn test_compact_blocks() {
+        let disk = DiskMap::parse("12345").unwrap().compact_blocks();
+        assert_eq!(disk.to_string(), "022111222......");
+        let disk = DiskMap::parse(INPUT).unwrap().compact_blocks();
+        assert_eq!(disk.to_string(), "0099811188827773336446555566..............");
+        assert_eq!(disk.checksum(), 1928);
+    }
+
+    #[test]
+    fn test_compact_files() {
+        let disk = DiskMap::parse(INPUT).unwrap().compact_files();
+        assert_eq!(disk.to_string(), "00992111777.44.333....5555.6666.....8888..");
+        assert_eq!(disk.checksum(), 2858);
+    }
+}
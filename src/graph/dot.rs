@@ -0,0 +1,63 @@
+use std::collections::HashSet;
+use std::fmt::Write;
+
+use super::{Connections, Graph};
+
+const HIGHLIGHT: &str = ", color=red, penwidth=2";
+
+impl <T> Graph<T>
+{
+    /// The graph in Graphviz DOT format, to render with e.g. `dot -Tsvg`. Nodes are named by their id and labelled
+    /// by `label`, connections are labelled by their weight. The nodes of `path`, given by id, and the connections
+    /// between consecutive path nodes are highlighted.
+    pub fn to_dot<L, W>(&self, label: L, connections: W, path: Option<&[usize]>) -> String where
+        L: Fn(&T) -> String,
+        W: Connections<T>,
+    {
+        let path_ids = path.unwrap_or_default();
+        let path_nodes: HashSet<usize> = path_ids.iter().copied().collect();
+        let path_edges: HashSet<(usize, usize)> = path_ids.windows(2).map(|w| (w[0].min(w[1]), w[0].max(w[1]))).collect();
+
+        let mut dot = String::from("graph {\n");
+        for (id, node) in self.nodes.iter().enumerate() {
+            let highlight = if path_nodes.contains(&id) { HIGHLIGHT } else { "" };
+            writeln!(dot, "    {} [label=\"{}\"{}];", id, escape(&label(node)), highlight).unwrap();
+        }
+        for (from, to) in connections.adjacency(&self.nodes).iter().enumerate() {
+            // non-directed, so every connection is listed from both sides: only write it from the lowest id
+            for &(to, weight) in to.iter().filter(|(to, _)| *to >= from) {
+                let highlight = if path_edges.contains(&(from, to)) { HIGHLIGHT } else { "" };
+                writeln!(dot, "    {} -- {} [label=\"{}\"{}];", from, to, weight, highlight).unwrap();
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+fn escape(label: &str) -> String {
+    label.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_dot() {
+        let mut g = Graph::new();
+        for n in ["a", "b", "\"c\""] {
+            g.add_node(n);
+        }
+        g.add_edge(0, 1, 3);
+        g.add_edge(1, 2, 1);
+        assert_eq!(g.to_dot(|n| n.to_string(), g.edges(), Some(&[0, 1])), "graph {
+    0 [label=\"a\", color=red, penwidth=2];
+    1 [label=\"b\", color=red, penwidth=2];
+    2 [label=\"\\\"c\\\"\"];
+    0 -- 1 [label=\"3\", color=red, penwidth=2];
+    1 -- 2 [label=\"1\"];
+}
+");
+    }
+}
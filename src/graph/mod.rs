@@ -1,4 +1,13 @@
-use std::ptr::eq;
+pub mod union_find;
+mod dot;
+pub mod search;
+mod structure;
+
+use std::borrow::Cow;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+
+use crate::map::{Mapp, Neighbours};
 
 /// A non-directed graph.
 /// In Rust, a generic graph is impossible: for more information see
@@ -6,111 +15,454 @@ use std::ptr::eq;
 /// https://stackoverflow.com/questions/28608823/how-to-model-complex-recursive-data-structures-graphs
 /// The alternative is to make a struct owning the nodes only and methods to the needed graphing
 /// specifically optimised for our algorithm.
+/// Connections are either given per call as a weight function (see `Connections`), or stored in the graph as `Edges`
+/// between node ids, the index of a node in `nodes`.
+#[derive(Default)]
 pub struct Graph<T>
 {
     pub nodes: Vec<T>,
+    edges: Edges,
+}
+
+/// The connections of the graph nodes, needed by all graph algorithms
+pub trait Connections<T> {
+    /// Connections per node id, as (id of connected node, weight) pairs
+    fn adjacency(&self, nodes: &[T]) -> Cow<'_, [Vec<(usize, usize)>]>;
+}
+
+/// A weight function gives the weight of the connection between two nodes, or None if not connected.
+/// It is called for every pair of nodes, so prefer stored `Edges` for large graphs.
+impl <T, W> Connections<T> for W where
+    W: Fn(&T, &T) -> Option<usize>,
+{
+    fn adjacency(&self, nodes: &[T]) -> Cow<'_, [Vec<(usize, usize)>]> {
+        nodes.iter()
+        .map(|from| nodes.iter()
+            .enumerate()
+            .filter_map(|(i, to)| self(from, to).map(|w| (i, w)))
+            .collect())
+        .collect()
+    }
+}
+
+/// Weighted connections stored per node id, see `Graph::add_edge`
+#[derive(Default, Clone)]
+pub struct Edges(Vec<Vec<(usize, usize)>>);
+
+impl <T> Connections<T> for &Edges {
+    fn adjacency(&self, nodes: &[T]) -> Cow<'_, [Vec<(usize, usize)>]> {
+        if self.0.len() >= nodes.len() {
+            Cow::Borrowed(&self.0)
+        } else { // nodes were pushed without edges
+            let mut padded = self.0.clone();
+            padded.resize(nodes.len(), Vec::new());
+            Cow::Owned(padded)
+        }
+    }
 }
 
 impl <T> Graph<T>
 {
     pub fn new() -> Self {
-        Graph { nodes: Vec::new() }
+        Graph { nodes: Vec::new(), edges: Edges::default() }
+    }
+
+    /// Adds a node and returns its id
+    pub fn add_node(&mut self, node: T) -> usize {
+        self.nodes.push(node);
+        self.nodes.len() - 1
     }
-    
-    fn connections<W>(&self, from: &T, weigth: &W) -> Vec<(&T, usize)> where 
-        W: Fn(&T, &T) -> Option<usize>, // W gives weight of connection between two nodes, or None if not connected
+
+    /// Stores a non-directed connection between the nodes with ids `a` and `b`.
+    /// Panics if either id is not the id of a node, so add the nodes first.
+    pub fn add_edge(&mut self, a: usize, b: usize, weight: usize) {
+        assert!(a < self.nodes.len() && b < self.nodes.len(),
+            "Cannot connect nodes {} and {}, the graph has {} nodes", a, b, self.nodes.len());
+        let needed = a.max(b) + 1;
+        if self.edges.0.len() < needed {
+            self.edges.0.resize(needed, Vec::new());
+        }
+        self.edges.0[a].push((b, weight));
+        if a != b {
+            self.edges.0[b].push((a, weight));
+        }
+    }
+
+    /// The stored connections, to be passed to any algorithm taking `Connections`
+    pub fn edges(&self) -> &Edges {
+        &self.edges
+    }
+
+    /// (id, weight) of the nodes connected to the node with id `id` by stored edges
+    pub fn neighbours(&self, id: usize) -> &[(usize, usize)] {
+        self.edges.0.get(id).map_or(&[], Vec::as_slice)
+    }
+
+    fn index_where<P>(&self, predicate: P) -> usize where
+        P: Fn(&T) -> bool,
     {
-        self.nodes.iter()
-        .filter_map(|n| weigth(from, n).map(|w| (n, w)))
-        .collect()
+        self.nodes.iter().position(predicate).expect("No graph node matches the predicate")
     }
 
+    /// Dijkstra's algorithm to calculate the minimum distance between the start node and the nearest end node.
+    /// The search stops as soon as an end node is settled, i.e. once its distance can no longer improve.
+    /// Returns None if no end node is reachable. Panics if `is_start` does not return true for any graph node.
     pub fn minimum_distance<S, E, W>(&self, is_start: S, is_end: E, weight: W) -> Option<usize> where
         S: Fn(&T) -> bool,
         E: Fn(&T) -> bool,
-        W: Fn(&T, &T) -> Option<usize>, // W gives weight of connection between two nodes, or None if not connected
+        W: Connections<T>,
+    {
+        self.minimum_distance_private(is_start, is_end, weight, None)
+    }
+
+    /// Like `minimum_distance`, but gives up once every unsettled node is further away than `max_distance`.
+    /// Returns the exact minimum distance if it is at most `max_distance`, None otherwise.
+    pub fn minimum_distance_within<S, E, W>(&self, is_start: S, is_end: E, weight: W, max_distance: usize) -> Option<usize> where
+        S: Fn(&T) -> bool,
+        E: Fn(&T) -> bool,
+        W: Connections<T>,
     {
-        self.distances_private::<S, E, W>(is_start, weight, None, None).iter()
-        .find(|(node, dist)| is_end(node) && *dist < usize::MAX)
-        .map(|(_, dist)| *dist)
+        self.minimum_distance_private(is_start, is_end, weight, Some(max_distance))
     }
 
-    /// Dijkstra's algorithm to calculate minumum distance between `start` and `end`.
-    /// Uses https://doc.rust-lang.org/std/ptr/fn.eq.html internally to compare &Ts, which is why the start and end
-    /// are supplied as functions (because raw pointers should point to graph.nodes entries, which could easily be
-    /// mistaken) - in case is_start / is_end does not return true for any graph node, this function will panic
-    pub fn minimum_distance_bounded<S, E, W>(&self, is_start: S, is_end: E, weight: W, lower_bound: usize) -> Option<usize> where
+    /// Whether an end node can be reached from the start node with a total weight of at most `max_distance`
+    pub fn is_reachable_within<S, E, W>(&self, is_start: S, is_end: E, weight: W, max_distance: usize) -> bool where
         S: Fn(&T) -> bool,
         E: Fn(&T) -> bool,
-        W: Fn(&T, &T) -> Option<usize>, // W gives weight of connection between two nodes, or None if not connected
+        W: Connections<T>,
     {
-        self.distances_private::<S, E, W>(is_start, weight, Some(&is_end), Some(lower_bound)).iter()
-        .find(|(node, dist)| is_end(node) && *dist < usize::MAX)
-        .map(|(_, dist)| *dist)
+        self.minimum_distance_within(is_start, is_end, weight, max_distance).is_some()
     }
 
+    /// Distances of all nodes to the start node, usize::MAX for nodes that cannot be reached
     pub fn distances<S, W>(&self, is_start: S, weight: W) -> Vec<(&T, usize)> where
         S: Fn(&T) -> bool,
-        W: Fn(&T, &T) -> Option<usize>,
+        W: Connections<T>,
     {
-        self.distances_private::<S, Box<dyn Fn(&T) -> bool>, W>(is_start, weight, None, None)
+        let start = self.index_where(is_start);
+        self.nodes.iter()
+        .zip(self.dijkstra(start, &weight.adjacency(&self.nodes), |_| false, None))
+        .map(|(node, dist)| (node, dist.unwrap_or(usize::MAX)))
+        .collect()
+    }
+
+    /// Distances of all nodes to the start node, looked up by node id (the index of the node in `nodes`)
+    pub fn distance_map<S, W>(&self, is_start: S, weight: W) -> DistanceMap where
+        S: Fn(&T) -> bool,
+        W: Connections<T>,
+    {
+        let start = self.index_where(is_start);
+        DistanceMap(self.dijkstra(start, &weight.adjacency(&self.nodes), |_| false, None))
+    }
+
+    /// Distances between all pairs of nodes. Uses a breadth first search per node when all connections have weight 1,
+    /// Floyd–Warshall for dense graphs and Dijkstra per node otherwise.
+    pub fn all_pairs<W>(&self, weight: W) -> DistanceMatrix where
+        W: Connections<T>,
+    {
+        let adjacency = weight.adjacency(&self.nodes);
+        let size = self.nodes.len();
+        let edge_count: usize = adjacency.iter().map(Vec::len).sum();
+
+        if adjacency.iter().flatten().all(|(_, w)| *w == 1) {
+            DistanceMatrix { size, distances: (0..size).flat_map(|from| bfs(from, &adjacency)).collect() }
+        } else if 4 * edge_count >= size * size {
+            floyd_warshall(&adjacency)
+        } else {
+            DistanceMatrix { size, distances: (0..size).flat_map(|from| self.dijkstra(from, &adjacency, |_| false, None)).collect() }
+        }
     }
-        
-    fn distances_private<S, E, W>(&self, is_start: S, weight: W, end: Option<&E>, lower_bound: Option<usize>) -> Vec<(&T, usize)> where
+
+    fn minimum_distance_private<S, E, W>(&self, is_start: S, is_end: E, weight: W, max_distance: Option<usize>) -> Option<usize> where
         S: Fn(&T) -> bool,
         E: Fn(&T) -> bool,
-        W: Fn(&T, &T) -> Option<usize>,
+        W: Connections<T>,
     {
-        // `table` contains references to all nodes (table.0), and as prescribed by the Dijkstra's algorithm:
-        // a distance (table.1), and the visited status (table.2)
-        let mut table: Vec<(&T, usize, bool)> = self.nodes.iter()
-            .map(|n| (n, usize::MAX, false))
-            .collect();
-
-        let start_index = table.iter().position(|(n, _, _)| is_start(n)).unwrap();
-        let end_index: Option<usize> = end.map(|is_end| {
-            table.iter().position(|(n, _, _)| is_end(n)).unwrap()
-        });
-        
-        table[start_index].1 = 0;
-        
-        'algo: while table.iter().any(|(_, _, visited)| !visited) {
-            if let Some(current_node) = table.iter()
-                .filter(|(_, _, visited)| !visited )
-                .fold((None, usize::MAX),
-                    |(min_dist_node, min_dist), (n, d, _)| {
-                        if *d < min_dist { (Some(*n), *d) } 
-                        else { (min_dist_node, min_dist) }
-                })
-                .0 {
-
-                let current_node_index = table.iter().position(|(n, _, _)| eq(*n, current_node)).unwrap();
-                table[current_node_index].2 = true;
-
-                // for all connections to current node, check if the route via the current node is the first or
-                // in case a route already exists, whether the current route is shorter. In both cases, update
-                // the distance of the connected node in our table
-                let curr_dist = table[current_node_index].1;
-                for (to, w) in self.connections(current_node, &weight) {
-                    let to_node_index = table.iter().position(|(n, _, _)| eq(*n, to)).unwrap();
-                    if !table[to_node_index].2 {
-                        let to_distance_via_curr_node = curr_dist + w;
-                        if to_distance_via_curr_node < table[to_node_index].1 {
-                            table[to_node_index].1 = to_distance_via_curr_node;
-
-                            if end_index.is_some_and(|i| i == to_node_index) 
-                            && lower_bound.is_some_and(|x| x >= to_distance_via_curr_node) {
-                                // Stopping distance calculations, since end node closer than lower bound was found
-                                break 'algo;
-                            }
+        let start = self.index_where(is_start);
+        let is_end: Vec<bool> = self.nodes.iter().map(is_end).collect();
+        self.dijkstra(start, &weight.adjacency(&self.nodes), |i| is_end[i], max_distance).into_iter()
+        .enumerate()
+        .filter(|(i, _)| is_end[*i])
+        .filter_map(|(_, dist)| dist)
+        .min()
+    }
+
+    /// Settled distances per node index. Settling stops after the first node for which `stop_at` is true, or before
+    /// the first node further away than `max_distance`. Nodes that were not settled get None: their tentative
+    /// distance is not guaranteed to be minimal, so it is not reported.
+    fn dijkstra<F>(&self, start: usize, adjacency: &[Vec<(usize, usize)>], stop_at: F, max_distance: Option<usize>) -> Vec<Option<usize>> where
+        F: Fn(usize) -> bool,
+    {
+        let mut tentative = vec![usize::MAX; self.nodes.len()];
+        let mut settled: Vec<Option<usize>> = vec![None; self.nodes.len()];
+        let mut queue = BinaryHeap::new();
+        tentative[start] = 0;
+        queue.push(Reverse((0, start)));
+
+        while let Some(Reverse((dist, current))) = queue.pop() {
+            if settled[current].is_some() {
+                continue; // a stale queue entry, the node was already settled via a shorter route
+            }
+            if max_distance.is_some_and(|max| dist > max) {
+                break; // the queue is ordered, so all remaining nodes are too far away as well
+            }
+            settled[current] = Some(dist);
+            if stop_at(current) {
+                break;
+            }
+
+            // for all connections to current node, check if the route via the current node is the first or
+            // in case a route already exists, whether the current route is shorter
+            for &(to, w) in &adjacency[current] {
+                let via_current = dist + w;
+                if settled[to].is_none() && via_current < tentative[to] {
+                    tentative[to] = via_current;
+                    queue.push(Reverse((via_current, to)));
+                }
+            }
+        }
+
+        settled
+    }
+}
+
+impl Graph<(usize, usize)> {
+    /// A graph of the (x, y) positions of the passable map cells, with edges of weight 1 between passable
+    /// neighbouring cells. Node ids follow the map row by row.
+    pub fn from_grid<T, P>(map: &Mapp<T>, passable: P, neighbours: Neighbours) -> Self where
+        P: Fn(&T) -> bool,
+    {
+        let mut graph = Graph::new();
+        let mut ids: Vec<Option<usize>> = vec![None; map.width() * map.height()];
+        for y in 0..map.height() {
+            for x in 0..map.width() {
+                if map.get(x, y).is_some_and(&passable) {
+                    ids[y * map.width() + x] = Some(graph.add_node((x, y)));
+                }
+            }
+        }
+        graph.edges.0.resize(graph.nodes.len(), Vec::new());
+        for id in 0..graph.nodes.len() {
+            let (x, y) = graph.nodes[id];
+            for (nx, ny) in map.neighbours(x, y, neighbours) {
+                if let Some(other) = ids[ny * map.width() + nx] {
+                    graph.edges.0[id].push((other, 1));
+                }
+            }
+        }
+        graph
+    }
+
+    /// All pairs of grid nodes at most `radius` apart in Manhattan distance, as (id, other id, distance) with
+    /// id < other id. Enumerates the diamond around every node, so this is O(nodes * radius²) instead of O(nodes²).
+    pub fn pairs_within(&self, radius: usize) -> Vec<(usize, usize, usize)> {
+        let ids: HashMap<(usize, usize), usize> = self.nodes.iter().enumerate().map(|(id, n)| (*n, id)).collect();
+        let r = radius as isize;
+        let mut pairs = Vec::new();
+        for (id, &(x, y)) in self.nodes.iter().enumerate() {
+            for dy in -r..=r {
+                let rx = r - dy.abs();
+                for dx in -rx..=rx {
+                    let other = (x.checked_add_signed(dx), y.checked_add_signed(dy));
+                    if let (Some(ox), Some(oy)) = other {
+                        match ids.get(&(ox, oy)) {
+                            Some(&other_id) if other_id > id => pairs.push((id, other_id, dx.unsigned_abs() + dy.unsigned_abs())),
+                            _ => {},
                         }
                     }
                 }
-            } else { // no current node means the graph is not connected and we processed all that is connected to start
-                break;
             }
         }
+        pairs
+    }
+}
+
+/// Distances from a single start node per node id, None for nodes that cannot be reached
+pub struct DistanceMap(Vec<Option<usize>>);
+
+impl DistanceMap {
+    pub fn get(&self, id: usize) -> Option<usize> {
+        self.0.get(id).copied().flatten()
+    }
+
+    /// (id, distance) of all reachable nodes
+    pub fn iter(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.0.iter().enumerate().filter_map(|(id, d)| d.map(|d| (id, d)))
+    }
+}
+
+/// Distances between all pairs of nodes, None for pairs that are not connected
+pub struct DistanceMatrix {
+    size: usize,
+    distances: Vec<Option<usize>>,
+}
+
+impl DistanceMatrix {
+    pub fn get(&self, from: usize, to: usize) -> Option<usize> {
+        if from < self.size && to < self.size { self.distances[from * self.size + to] } else { None }
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+}
+
+fn bfs(start: usize, adjacency: &[Vec<(usize, usize)>]) -> Vec<Option<usize>> {
+    let mut distances = vec![None; adjacency.len()];
+    let mut queue = VecDeque::from([start]);
+    distances[start] = Some(0);
+    while let Some(current) = queue.pop_front() {
+        let next = distances[current].map(|d| d + 1);
+        for &(to, _) in &adjacency[current] {
+            if distances[to].is_none() {
+                distances[to] = next;
+                queue.push_back(to);
+            }
+        }
+    }
+    distances
+}
+
+fn floyd_warshall(adjacency: &[Vec<(usize, usize)>]) -> DistanceMatrix {
+    let size = adjacency.len();
+    let mut distances = vec![None; size * size];
+    for (from, connections) in adjacency.iter().enumerate() {
+        distances[from * size + from] = Some(0);
+        for &(to, w) in connections {
+            let current = &mut distances[from * size + to];
+            *current = Some(current.map_or(w, |d| d.min(w)));
+        }
+    }
+    for via in 0..size {
+        for from in 0..size {
+            let Some(to_via) = distances[from * size + via] else { continue };
+            for to in 0..size {
+                if let Some(from_via) = distances[via * size + to] {
+                    let current = &mut distances[from * size + to];
+                    if current.is_none_or(|d| to_via + from_via < d) {
+                        *current = Some(to_via + from_via);
+                    }
+                }
+            }
+        }
+    }
+    DistanceMatrix { size, distances }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::map::read_map;
+
+    // Nodes are single chars and connections are listed explicitly, so tests can describe any graph
+    fn weight_from(edges: &'static [(char, char, usize)]) -> impl Fn(&char, &char) -> Option<usize> {
+        |a, b| edges.iter()
+            .find(|(from, to, _)| (from == a && to == b) || (from == b && to == a))
+            .map(|(_, _, w)| *w)
+    }
+
+    fn graph(nodes: &str) -> Graph<char> {
+        Graph { nodes: nodes.chars().collect(), edges: Edges::default() }
+    }
+
+    // The direct connection s-e is found first, but the detour via a, b and c is shorter
+    const DETOUR: &[(char, char, usize)] = &[('s', 'e', 10), ('s', 'a', 1), ('a', 'b', 1), ('b', 'c', 1), ('c', 'e', 1)];
+
+    #[test]
+    fn test_minimum_distance_waits_until_end_is_settled() {
+        let g = graph("seabc");
+        assert_eq!(g.minimum_distance(|n| *n == 's', |n| *n == 'e', weight_from(DETOUR)), Some(4));
+    }
+
+    #[test]
+    fn test_minimum_distance_multiple_ends_and_unreachable() {
+        let g = graph("seabcxy");
+        assert_eq!(g.minimum_distance(|n| *n == 's', |n| *n == 'e' || *n == 'b', weight_from(DETOUR)), Some(2));
+        assert_eq!(g.minimum_distance(|n| *n == 's', |n| *n == 'x', weight_from(DETOUR)), None);
+        assert_eq!(g.minimum_distance(|n| *n == 'x', |n| *n == 'x', weight_from(DETOUR)), Some(0));
+    }
+
+    #[test]
+    fn test_minimum_distance_within() {
+        let g = graph("seabc");
+        let within = |max| g.minimum_distance_within(|n| *n == 's', |n| *n == 'e', weight_from(DETOUR), max);
+        assert_eq!(within(3), None);
+        assert_eq!(within(4), Some(4));
+        assert_eq!(within(100), Some(4));
+        assert!(!g.is_reachable_within(|n| *n == 's', |n| *n == 'e', weight_from(DETOUR), 3));
+        assert!(g.is_reachable_within(|n| *n == 's', |n| *n == 'e', weight_from(DETOUR), 4));
+    }
+
+    #[test]
+    fn test_zero_weights_and_distances() {
+        const ZERO: &[(char, char, usize)] = &[('s', 'a', 0), ('a', 'b', 0), ('s', 'b', 3), ('b', 'e', 2)];
+        let g = graph("sabex");
+        assert_eq!(g.minimum_distance(|n| *n == 's', |n| *n == 'e', weight_from(ZERO)), Some(2));
+        let distances: Vec<_> = g.distances(|n| *n == 's', weight_from(ZERO)).into_iter().map(|(_, d)| d).collect();
+        assert_eq!(distances, vec![0, 0, 0, 2, usize::MAX]);
+    }
+
+    #[test]
+    fn test_distance_map_and_all_pairs() {
+        let g = graph("seabcxyz");
+        let map = g.distance_map(|n| *n == 's', weight_from(DETOUR));
+        assert_eq!((map.get(1), map.get(4), map.get(5), map.get(6)), (Some(4), Some(3), None, None));
+        assert_eq!(map.iter().count(), 5);
+        assert_eq!(map.get(8), None);
+
+        // weighted, with both Dijkstra per node (sparse) and Floyd–Warshall (dense)
+        for g in [g, graph("seabc")] {
+            let all = g.all_pairs(weight_from(DETOUR));
+            assert_eq!((all.get(0, 1), all.get(1, 0), all.get(2, 4), all.get(3, 3)), (Some(4), Some(4), Some(2), Some(0)));
+            assert_eq!(all.get(0, 5), None);
+        }
+
+        // unit weights
+        const PATH: &[(char, char, usize)] = &[('a', 'b', 1), ('b', 'c', 1), ('c', 'd', 1)];
+        let all = graph("abcde").all_pairs(weight_from(PATH));
+        assert_eq!((all.get(0, 3), all.get(3, 1), all.get(0, 4), all.size()), (Some(3), Some(2), None, 5));
+    }
+
+    #[test]
+    fn test_pairs_within() {
+        let g = Graph { nodes: vec![(0, 0), (1, 0), (2, 0), (0, 2), (2, 2)], edges: Edges::default() };
+        let mut pairs = g.pairs_within(2);
+        pairs.sort();
+        assert_eq!(pairs, vec![(0, 1, 1), (0, 2, 2), (0, 3, 2), (1, 2, 1), (2, 4, 2), (3, 4, 2)]);
+        assert_eq!(g.pairs_within(0), vec![]);
+    }
+
+    #[test]
+    fn test_from_grid() {
+        let map = read_map(vec![String::from("S.#"), String::from(".#."), String::from("..E")]);
+        let g = Graph::from_grid(&map, |c| *c != '#', Neighbours::Dir4);
+        assert_eq!(g.nodes, vec![(0, 0), (1, 0), (0, 1), (2, 1), (0, 2), (1, 2), (2, 2)]);
+        assert_eq!(g.neighbours(0), &[(1, 1), (2, 1)]);
+        assert_eq!(g.minimum_distance(|n| *n == (0, 0), |n| *n == (2, 2), g.edges()), Some(4));
+
+        let g = Graph::from_grid(&map, |c| *c != '#', Neighbours::Dir8);
+        assert_eq!(g.minimum_distance(|n| *n == (0, 0), |n| *n == (2, 2), g.edges()), Some(3));
+        assert_eq!(g.minimum_distance(|n| *n == (1, 0), |n| *n == (2, 1), g.edges()), Some(1));
+    }
+
+    #[test]
+    fn test_add_edge() {
+        let mut g = Graph::new();
+        let a = g.add_node('a');
+        let b = g.add_node('b');
+        let c = g.add_node('c');
+        g.add_edge(a, b, 2);
+        g.add_node('d');
+        assert_eq!(g.minimum_distance(|n| *n == 'a', |n| *n == 'b', g.edges()), Some(2));
+        assert_eq!(g.minimum_distance(|n| *n == 'b', |n| *n == 'd', g.edges()), None);
+        g.add_edge(b, c, 3);
+        assert_eq!(g.minimum_distance(|n| *n == 'c', |n| *n == 'a', g.edges()), Some(5));
+    }
 
-        table.into_iter().map(|(node, dist, _)| (node, dist)).collect()
+    #[test]
+    #[should_panic(expected = "Cannot connect nodes 0 and 5, the graph has 3 nodes")]
+    fn test_add_edge_to_missing_node() {
+        let mut g = graph("abc");
+        g.add_edge(0, 5, 1);
     }
 }
@@ -0,0 +1,132 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::hash::Hash;
+
+/// The result of Dijkstra's algorithm on a directed graph of states that is only known through a successor function,
+/// for when the states are too many or too irregular to put in a `Graph` first
+pub struct ShortestPaths<S> {
+    distances: HashMap<S, usize>,
+    /// Per settled state: the states it is reached from on some shortest path
+    predecessors: HashMap<S, Vec<S>>,
+}
+
+/// Settles every state reachable from the start states. `successors` gives the (state, weight) pairs reachable in one
+/// move from a state.
+pub fn shortest_paths<S, F, I>(starts: impl IntoIterator<Item = S>, successors: F) -> ShortestPaths<S> where
+    S: Clone + Eq + Hash + Ord,
+    F: Fn(&S) -> I,
+    I: IntoIterator<Item = (S, usize)>,
+{
+    let mut tentative: HashMap<S, usize> = HashMap::new();
+    let mut predecessors: HashMap<S, Vec<S>> = HashMap::new();
+    let mut distances = HashMap::new();
+    let mut queue = BinaryHeap::new();
+    for start in starts {
+        tentative.insert(start.clone(), 0);
+        queue.push(Reverse((0, start)));
+    }
+
+    while let Some(Reverse((dist, current))) = queue.pop() {
+        if distances.contains_key(&current) {
+            continue; // a stale queue entry
+        }
+        distances.insert(current.clone(), dist);
+        for (next, w) in successors(&current) {
+            let via_current = dist + w;
+            if let Some(&settled) = distances.get(&next) {
+                // only possible with a zero weight: settled at the same distance, but still a shortest path
+                if settled == via_current {
+                    predecessors.entry(next).or_default().push(current.clone());
+                }
+                continue;
+            }
+            match tentative.get(&next) {
+                Some(&d) if d < via_current => {},
+                Some(&d) if d == via_current => predecessors.entry(next).or_default().push(current.clone()),
+                _ => {
+                    tentative.insert(next.clone(), via_current);
+                    predecessors.insert(next.clone(), vec![current.clone()]);
+                    queue.push(Reverse((via_current, next)));
+                },
+            }
+        }
+    }
+
+    ShortestPaths { distances, predecessors }
+}
+
+impl <S> ShortestPaths<S> where
+    S: Clone + Eq + Hash,
+{
+    /// The minimum distance from any start state, None if `state` cannot be reached
+    pub fn distance(&self, state: &S) -> Option<usize> {
+        self.distances.get(state).copied()
+    }
+
+    /// The nearest of the `ends` with its distance, None if none can be reached
+    pub fn nearest<'a>(&self, ends: impl IntoIterator<Item = &'a S>) -> Option<(&'a S, usize)> where
+        S: 'a,
+    {
+        ends.into_iter().filter_map(|end| Some((end, self.distance(end)?))).min_by_key(|&(_, dist)| dist)
+    }
+
+    /// All states on any shortest path from a start state to the nearest of the `ends`, including the ends
+    /// themselves when more than one of them is nearest
+    pub fn on_shortest_paths<'a>(&self, ends: impl IntoIterator<Item = &'a S>) -> HashSet<S> where
+        S: 'a,
+    {
+        let ends: Vec<_> = ends.into_iter().collect();
+        let Some((_, min)) = self.nearest(ends.iter().copied()) else { return HashSet::new() };
+        let mut stack: Vec<S> = ends.into_iter().filter(|end| self.distance(end) == Some(min)).cloned().collect();
+        let mut seen: HashSet<S> = stack.iter().cloned().collect();
+        while let Some(state) = stack.pop() {
+            for previous in self.predecessors.get(&state).into_iter().flatten() {
+                if seen.insert(previous.clone()) {
+                    stack.push(previous.clone());
+                }
+            }
+        }
+        seen
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shortest_paths() {
+        // numbers from -10 to 10, where stepping from n to n + 1 costs 1 and to n + 2 costs 2, so there are many
+        // equally short paths, and stepping back from n to n - 3 costs nothing
+        let paths = shortest_paths([0], |&n: &i32| {
+            [(n + 1, 1), (n + 2, 2), (n - 3, 0)].into_iter().filter(|(n, _)| (-10..10).contains(n))
+        });
+        assert_eq!(paths.distance(&0), Some(0));
+        assert_eq!(paths.distance(&5), Some(5));
+        assert_eq!(paths.distance(&-3), Some(0));
+        assert_eq!(paths.distance(&10), None);
+        assert_eq!(paths.nearest(&[5, 4, 10]), Some((&4, 4)));
+
+        let mut on_paths: Vec<_> = paths.on_shortest_paths(&[2]).into_iter().collect();
+        on_paths.sort();
+        assert_eq!(on_paths, vec![0, 1, 2]);
+
+        // s-b-a is as short as s-a thanks to the zero weight, even though a is settled before b is looked at
+        let paths = shortest_paths(['s'], |&c: &char| match c {
+            's' => vec![('a', 1), ('b', 1)],
+            'b' => vec![('a', 0)],
+            _ => vec![],
+        });
+        let mut on_paths: Vec<_> = paths.on_shortest_paths(&['a']).into_iter().collect();
+        on_paths.sort();
+        assert_eq!(on_paths, vec!['a', 'b', 's']);
+    }
+
+    #[test]
+    fn test_directed() {
+        // a one way street: b can be reached from a, not the other way around
+        let paths = shortest_paths(['b'], |&c: &char| if c == 'a' { vec![('b', 1)] } else { vec![] });
+        assert_eq!(paths.distance(&'a'), None);
+        assert!(paths.on_shortest_paths(&['a']).is_empty());
+    }
+}
@@ -0,0 +1,273 @@
+use std::collections::{HashSet, VecDeque};
+
+use super::union_find::UnionFind;
+use super::{Connections, Graph};
+
+/// Algorithms on the structure of the graph: connection weights are ignored, only whether nodes are connected matters
+impl <T> Graph<T>
+{
+    /// The connected components, each in node order
+    pub fn connected_components<W>(&self, connections: W) -> Vec<Vec<&T>> where
+        W: Connections<T>,
+    {
+        let mut sets = UnionFind::new(self.nodes.len());
+        for (from, to) in connections.adjacency(&self.nodes).iter().enumerate() {
+            for &(to, _) in to {
+                sets.union(from, to);
+            }
+        }
+        sets.sets().into_iter().map(|set| self.nodes_by_id(&set)).collect()
+    }
+
+    /// Connections whose removal would split a connected component in two
+    pub fn bridges<W>(&self, connections: W) -> Vec<(&T, &T)> where
+        W: Connections<T>,
+    {
+        lowlink(&connections.adjacency(&self.nodes)).0.into_iter()
+        .map(|(a, b)| (&self.nodes[a], &self.nodes[b]))
+        .collect()
+    }
+
+    /// Nodes whose removal would split a connected component, in node order
+    pub fn articulation_points<W>(&self, connections: W) -> Vec<&T> where
+        W: Connections<T>,
+    {
+        let is_articulation = lowlink(&connections.adjacency(&self.nodes)).1;
+        self.nodes.iter().zip(is_articulation).filter(|(_, a)| *a).map(|(n, _)| n).collect()
+    }
+
+    /// Splits the nodes in two sides such that every connection is between both sides, or None if that is impossible
+    /// (i.e. the graph has a cycle of odd length)
+    pub fn bipartition<W>(&self, connections: W) -> Option<(Vec<&T>, Vec<&T>)> where
+        W: Connections<T>,
+    {
+        let adjacency = connections.adjacency(&self.nodes);
+        let mut side: Vec<Option<bool>> = vec![None; self.nodes.len()];
+        for root in 0..self.nodes.len() {
+            if side[root].is_some() {
+                continue;
+            }
+            side[root] = Some(false);
+            let mut queue = VecDeque::from([root]);
+            while let Some(current) = queue.pop_front() {
+                let other_side = side[current].map(|s| !s);
+                for &(to, _) in &adjacency[current] {
+                    match side[to] {
+                        None => {
+                            side[to] = other_side;
+                            queue.push_back(to);
+                        },
+                        Some(s) if Some(s) != other_side => return None,
+                        _ => {},
+                    }
+                }
+            }
+        }
+        let (left, right): (Vec<_>, Vec<_>) = self.nodes.iter().zip(side).partition(|(_, s)| *s == Some(false));
+        Some((left.into_iter().map(|(n, _)| n).collect(), right.into_iter().map(|(n, _)| n).collect()))
+    }
+
+    pub fn is_bipartite<W>(&self, connections: W) -> bool where
+        W: Connections<T>,
+    {
+        self.bipartition(connections).is_some()
+    }
+
+    /// All maximal cliques (sets of nodes that are all connected to each other and cannot be extended), each in node
+    /// order and listed in node order. Uses Bron–Kerbosch with pivoting.
+    pub fn maximal_cliques<W>(&self, connections: W) -> Vec<Vec<&T>> where
+        W: Connections<T>,
+    {
+        self.maximal_clique_ids(connections).iter().map(|clique| self.nodes_by_id(clique)).collect()
+    }
+
+    /// A largest clique, the first in node order in case of a tie
+    pub fn maximum_clique<W>(&self, connections: W) -> Vec<&T> where
+        W: Connections<T>,
+    {
+        let mut cliques = self.maximal_clique_ids(connections);
+        cliques.sort_by(|a, b| b.len().cmp(&a.len()).then_with(|| a.cmp(b)));
+        cliques.first().map(|clique| self.nodes_by_id(clique)).unwrap_or_default()
+    }
+
+    // sorted, as the order in which Bron–Kerbosch finds them depends on hash set iteration
+    fn maximal_clique_ids<W>(&self, connections: W) -> Vec<Vec<usize>> where
+        W: Connections<T>,
+    {
+        let neighbours: Vec<HashSet<usize>> = connections.adjacency(&self.nodes).iter().enumerate()
+            .map(|(from, to)| to.iter().map(|(to, _)| *to).filter(|to| *to != from).collect())
+            .collect();
+        let mut cliques = Vec::new();
+        bron_kerbosch(&neighbours, &mut Vec::new(), (0..self.nodes.len()).collect(), HashSet::new(), &mut cliques);
+        for clique in cliques.iter_mut() {
+            clique.sort();
+        }
+        cliques.sort();
+        cliques
+    }
+
+    fn nodes_by_id(&self, ids: &[usize]) -> Vec<&T> {
+        ids.iter().map(|&id| &self.nodes[id]).collect()
+    }
+}
+
+fn bron_kerbosch(neighbours: &[HashSet<usize>], clique: &mut Vec<usize>, mut candidates: HashSet<usize>, mut excluded: HashSet<usize>, cliques: &mut Vec<Vec<usize>>) {
+    if candidates.is_empty() {
+        if excluded.is_empty() {
+            cliques.push(clique.clone());
+        }
+        return;
+    }
+    // the pivot with the most candidate neighbours leaves the fewest nodes to branch on
+    let pivot = *candidates.union(&excluded)
+        .max_by_key(|&&p| neighbours[p].intersection(&candidates).count())
+        .unwrap();
+    let branches: Vec<usize> = candidates.difference(&neighbours[pivot]).copied().collect();
+    for node in branches {
+        clique.push(node);
+        bron_kerbosch(
+            neighbours,
+            clique,
+            candidates.intersection(&neighbours[node]).copied().collect(),
+            excluded.intersection(&neighbours[node]).copied().collect(),
+            cliques,
+        );
+        clique.pop();
+        candidates.remove(&node);
+        excluded.insert(node);
+    }
+}
+
+/// Tarjan's lowlink depth first search, returning the bridges and per node whether it is an articulation point.
+/// Iterative, so large grid graphs do not overflow the stack.
+fn lowlink(adjacency: &[Vec<(usize, usize)>]) -> (Vec<(usize, usize)>, Vec<bool>) {
+    let size = adjacency.len();
+    let mut discovered = vec![usize::MAX; size];
+    let mut low = vec![usize::MAX; size];
+    let mut is_articulation = vec![false; size];
+    let mut bridges = Vec::new();
+    let mut time = 0;
+
+    for root in 0..size {
+        if discovered[root] != usize::MAX {
+            continue;
+        }
+        discovered[root] = time;
+        low[root] = time;
+        time += 1;
+        let mut root_children = 0;
+        // (node, parent, index of the next connection to visit, whether the connection back to parent was skipped)
+        let mut stack: Vec<(usize, Option<usize>, usize, bool)> = vec![(root, None, 0, false)];
+
+        while let Some(frame) = stack.last_mut() {
+            let (current, parent) = (frame.0, frame.1);
+            if let Some(&(to, _)) = adjacency[current].get(frame.2) {
+                frame.2 += 1;
+                if to == current {
+                    continue;
+                }
+                if Some(to) == parent && !frame.3 { // a parallel connection to the parent is a real cycle though
+                    frame.3 = true;
+                    continue;
+                }
+                if discovered[to] == usize::MAX {
+                    discovered[to] = time;
+                    low[to] = time;
+                    time += 1;
+                    if current == root {
+                        root_children += 1;
+                    }
+                    stack.push((to, Some(current), 0, false));
+                } else {
+                    low[current] = low[current].min(discovered[to]);
+                }
+            } else {
+                stack.pop();
+                if let Some(parent) = parent {
+                    low[parent] = low[parent].min(low[current]);
+                    if low[current] > discovered[parent] {
+                        bridges.push((parent, current));
+                    }
+                    if parent != root && low[current] >= discovered[parent] {
+                        is_articulation[parent] = true;
+                    }
+                }
+            }
+        }
+        if root_children > 1 {
+            is_articulation[root] = true;
+        }
+    }
+
+    (bridges, is_articulation)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graph_with(nodes: &str, edges: &[(char, char)]) -> Graph<char> {
+        let mut g = Graph::new();
+        for n in nodes.chars() {
+            g.add_node(n);
+        }
+        let id = |c: char| nodes.find(c).unwrap();
+        for &(a, b) in edges {
+            g.add_edge(id(a), id(b), 1);
+        }
+        g
+    }
+
+    // Two triangles abc and def joined by the connection c-d, plus the loose node g
+    fn bowtie() -> Graph<char> {
+        graph_with("abcdefg", &[('a', 'b'), ('b', 'c'), ('c', 'a'), ('c', 'd'), ('d', 'e'), ('e', 'f'), ('f', 'd')])
+    }
+
+    #[test]
+    fn test_connected_components() {
+        let g = bowtie();
+        assert_eq!(g.connected_components(g.edges()), vec![vec![&'a', &'b', &'c', &'d', &'e', &'f'], vec![&'g']]);
+    }
+
+    #[test]
+    fn test_bridges_and_articulation_points() {
+        let g = bowtie();
+        assert_eq!(g.bridges(g.edges()), vec![(&'c', &'d')]);
+        assert_eq!(g.articulation_points(g.edges()), vec![&'c', &'d']);
+
+        // a parallel connection is no bridge, and a path has inner articulation points only
+        let g = graph_with("abc", &[('a', 'b'), ('a', 'b'), ('b', 'c')]);
+        assert_eq!(g.bridges(g.edges()), vec![(&'b', &'c')]);
+        assert_eq!(g.articulation_points(g.edges()), vec![&'b']);
+    }
+
+    #[test]
+    fn test_bipartition() {
+        let square = graph_with("abcd", &[('a', 'b'), ('b', 'c'), ('c', 'd'), ('d', 'a')]);
+        assert_eq!(square.bipartition(square.edges()), Some((vec![&'a', &'c'], vec![&'b', &'d'])));
+        let g = bowtie();
+        assert!(!g.is_bipartite(g.edges()));
+    }
+
+    #[test]
+    fn test_cliques() {
+        // the triangles of the bowtie, with the extra connections a-d, b-d making abcd the maximum clique
+        let mut g = bowtie();
+        g.add_edge(0, 3, 1);
+        g.add_edge(1, 3, 1);
+        let mut cliques = g.maximal_cliques(g.edges());
+        cliques.sort();
+        assert_eq!(cliques, vec![vec![&'a', &'b', &'c', &'d'], vec![&'d', &'e', &'f'], vec![&'g']]);
+        assert_eq!(g.maximum_clique(g.edges()), vec![&'a', &'b', &'c', &'d']);
+    }
+
+    #[test]
+    fn test_maximum_clique_tie() {
+        // three cliques of two, found in an order that varies from run to run
+        let g = graph_with("fedcba", &[('a', 'b'), ('c', 'd'), ('e', 'f')]);
+        for _ in 0..20 {
+            assert_eq!(g.maximum_clique(g.edges()), vec![&'f', &'e']);
+            assert_eq!(g.maximal_cliques(g.edges()), vec![vec![&'f', &'e'], vec![&'d', &'c'], vec![&'b', &'a']]);
+        }
+    }
+}
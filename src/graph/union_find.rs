@@ -0,0 +1,81 @@
+/// Disjoint sets of the numbers 0..size, with path compression and union by size
+pub struct UnionFind {
+    parents: Vec<usize>,
+    sizes: Vec<usize>,
+}
+
+impl UnionFind {
+    pub fn new(size: usize) -> Self {
+        UnionFind { parents: (0..size).collect(), sizes: vec![1; size] }
+    }
+
+    /// The representative of the set containing `x`
+    pub fn find(&mut self, x: usize) -> usize {
+        let mut root = x;
+        while self.parents[root] != root {
+            root = self.parents[root];
+        }
+        let mut current = x;
+        while self.parents[current] != root {
+            current = std::mem::replace(&mut self.parents[current], root);
+        }
+        root
+    }
+
+    /// Merges the sets containing `a` and `b`, returns false if they already were the same set
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (a, b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+        let (large, small) = if self.sizes[a] >= self.sizes[b] { (a, b) } else { (b, a) };
+        self.parents[small] = large;
+        self.sizes[large] += self.sizes[small];
+        true
+    }
+
+    pub fn same_set(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    /// The size of the set containing `x`
+    pub fn set_size(&mut self, x: usize) -> usize {
+        let root = self.find(x);
+        self.sizes[root]
+    }
+
+    /// All sets, each in ascending order, ordered by their smallest element
+    pub fn sets(&mut self) -> Vec<Vec<usize>> {
+        let mut set_index: Vec<Option<usize>> = vec![None; self.parents.len()];
+        let mut sets: Vec<Vec<usize>> = Vec::new();
+        for x in 0..self.parents.len() {
+            let root = self.find(x);
+            match set_index[root] {
+                Some(i) => sets[i].push(x),
+                None => {
+                    set_index[root] = Some(sets.len());
+                    sets.push(vec![x]);
+                },
+            }
+        }
+        sets
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_union_find() {
+        let mut uf = UnionFind::new(6);
+        assert!(uf.union(0, 1));
+        assert!(uf.union(3, 4));
+        assert!(uf.union(1, 4));
+        assert!(!uf.union(0, 3));
+        assert!(uf.same_set(0, 4));
+        assert!(!uf.same_set(0, 2));
+        assert_eq!(uf.set_size(3), 4);
+        assert_eq!(uf.sets(), vec![vec![0, 1, 3, 4], vec![2], vec![5]]);
+    }
+}
@@ -1,5 +1,19 @@
+pub mod calibration;
+pub mod collections;
+pub mod disk;
 pub mod graph;
 pub mod map;
+pub mod math;
+pub mod memo;
+pub mod parse;
+pub mod patrol;
+pub mod race;
+pub mod reindeer;
+pub mod stones;
+pub mod swarm;
+pub mod text;
+pub mod vm;
+pub mod warehouse;
 
 use std::fs::{metadata, File};
 use std::io::{self, BufRead, Error, Read};
@@ -13,7 +27,7 @@ where P: AsRef<Path>,
     match metadata(&filename) {
         Ok(meta) if meta.is_file() => (),
         Ok(_) => return Err(Error::new(io::ErrorKind::Unsupported, "Expected a file, but got a directory")),
-        Err(e) => return Err(Error::new(io::ErrorKind::Other, e)),
+        Err(e) => return Err(Error::other(e)),
     }
 
     let file = File::open(filename)?;
@@ -26,7 +40,7 @@ where P: AsRef<Path>,
     match metadata(&filename) {
         Ok(meta) if meta.is_file() => (),
         Ok(_) => return Err(Error::new(io::ErrorKind::Unsupported, "Expected a file, but got a directory")),
-        Err(e) => return Err(Error::new(io::ErrorKind::Other, e)),
+        Err(e) => return Err(Error::other(e)),
     }
 
     let file = File::open(filename)?;
@@ -49,4 +63,8 @@ pub mod test_util {
             .map(|line| Result::Ok(String::from(line)))
     }
 
+    /// A map from an indented, multi-line test input
+    pub fn map_from(input: &str) -> crate::map::Mapp<char> {
+        crate::map::read_map(io_lines_from(input).map(Result::unwrap).collect())
+    }
 }
@@ -1,10 +1,33 @@
+use std::fmt;
+
 pub struct Mapp<T>(Vec<Vec<T>>);
 
+/// Which cells count as neighbours: only horizontally and vertically adjacent cells, or diagonally adjacent ones too
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Neighbours {
+    Dir4,
+    Dir8,
+}
+
+impl Neighbours {
+    /// (dx, dy) offsets of the neighbouring cells
+    pub fn offsets(&self) -> &'static [(isize, isize)] {
+        match self {
+            Neighbours::Dir4 => &[(0, -1), (1, 0), (0, 1), (-1, 0)],
+            Neighbours::Dir8 => &[(0, -1), (1, -1), (1, 0), (1, 1), (0, 1), (-1, 1), (-1, 0), (-1, -1)],
+        }
+    }
+}
+
 impl <T> Mapp<T> {
     pub fn get(&self, x: usize, y: usize) -> Option<&T> {
         self.0.get(y)?.get(x)
     }
 
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        self.0.get_mut(y)?.get_mut(x)
+    }
+
     pub fn height(&self) -> usize {
         self.0.len()
     }
@@ -15,6 +38,14 @@ impl <T> Mapp<T> {
             Some(row) => row.len()
         }
     }
+
+    /// Positions of the neighbours of (x, y) that lie on the map
+    pub fn neighbours(&self, x: usize, y: usize, neighbours: Neighbours) -> impl Iterator<Item = (usize, usize)> + '_ {
+        neighbours.offsets().iter().filter_map(move |&(dx, dy)| {
+            let n = (x.checked_add_signed(dx)?, y.checked_add_signed(dy)?);
+            self.get(n.0, n.1).map(|_| n)
+        })
+    }
 }
 
 impl <T> Mapp<T> {
@@ -35,12 +66,17 @@ impl <T> Mapp<T> {
 
 impl Mapp<char> {
     pub fn print(&self) {
-        for y in 0..self.height() {
-            for x in 0..self.width() {
-                print!("{}", self.get(x, y).unwrap_or(&' '));
-            }
-            println!();
+        print!("{}", self);
+    }
+}
+
+/// The rows of the map, each followed by a newline
+impl fmt::Display for Mapp<char> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in &self.0 {
+            writeln!(f, "{}", row.iter().collect::<String>())?;
         }
+        Ok(())
     }
 }
 
@@ -0,0 +1,91 @@
+//! Exact solvers for small systems of linear equations with integer coefficients. Puzzles ask for whole, non-negative
+//! amounts (button presses, items), so only such solutions are returned. Intermediate values are i128, which keeps
+//! products of coefficients around 10^13 exact.
+
+/// Solves `a x = b` for a 2×2 system with Cramer's rule. Returns None if there is no unique solution, or if that
+/// solution is not whole and non-negative.
+pub fn solve_2x2(a: [[i64; 2]; 2], b: [i64; 2]) -> Option<[u64; 2]> {
+    let [[a00, a01], [a10, a11]] = a.map(|row| row.map(i128::from));
+    let [b0, b1] = b.map(i128::from);
+    let det = a00 * a11 - a01 * a10;
+    let x0 = whole_non_negative(b0 * a11 - a01 * b1, det)?;
+    let x1 = whole_non_negative(a00 * b1 - b0 * a10, det)?;
+    Some([x0, x1])
+}
+
+/// Solves `a x = b` for a square n×n system with Cramer's rule. Returns None if the system is not square, there is
+/// no unique solution, or if that solution is not whole and non-negative. Meant for small n: every unknown takes a
+/// determinant, which is computed in O(n³).
+pub fn solve(a: &[Vec<i64>], b: &[i64]) -> Option<Vec<u64>> {
+    let n = b.len();
+    if a.len() != n || a.iter().any(|row| row.len() != n) {
+        return None;
+    }
+    let matrix: Vec<Vec<i128>> = a.iter().map(|row| row.iter().map(|v| *v as i128).collect()).collect();
+    let det = determinant(matrix.clone());
+    (0..n).map(|column| {
+        let mut replaced = matrix.clone();
+        for (row, value) in replaced.iter_mut().zip(b) {
+            row[column] = *value as i128;
+        }
+        whole_non_negative(determinant(replaced), det)
+    })
+    .collect()
+}
+
+/// Determinant by fraction-free (Bareiss) elimination: every division is exact, so it stays in integers
+pub fn determinant(mut m: Vec<Vec<i128>>) -> i128 {
+    let n = m.len();
+    let mut sign = 1;
+    let mut previous_pivot = 1;
+    for k in 0..n {
+        if m[k][k] == 0 {
+            match (k + 1..n).find(|&row| m[row][k] != 0) {
+                Some(row) => {
+                    m.swap(k, row);
+                    sign = -sign;
+                },
+                None => return 0,
+            }
+        }
+        for i in k + 1..n {
+            for j in k + 1..n {
+                m[i][j] = (m[i][j] * m[k][k] - m[i][k] * m[k][j]) / previous_pivot;
+            }
+        }
+        previous_pivot = m[k][k];
+    }
+    if n == 0 { 1 } else { sign * m[n - 1][n - 1] }
+}
+
+fn whole_non_negative(numerator: i128, denominator: i128) -> Option<u64> {
+    if denominator == 0 || numerator % denominator != 0 {
+        return None;
+    }
+    u64::try_from(numerator / denominator).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solve_2x2() {
+        assert_eq!(solve_2x2([[94, 22], [34, 67]], [8400, 5400]), Some([80, 40]));
+        assert_eq!(solve_2x2([[26, 67], [66, 21]], [12748, 12176]), None);
+        assert_eq!(solve_2x2([[26, 67], [66, 21]], [10000000012748, 10000000012176]), Some([118679050709, 103199174542]));
+        assert_eq!(solve_2x2([[1, 2], [2, 4]], [3, 6]), None); // no unique solution
+        assert_eq!(solve_2x2([[1, 0], [0, 1]], [-1, 2]), None); // negative
+    }
+
+    #[test]
+    fn test_solve() {
+        let a = vec![vec![2, 1, -1], vec![-3, -1, 2], vec![-2, 1, 2]];
+        assert_eq!(solve(&a, &[6, -7, 1]), Some(vec![2, 3, 1]));
+        assert_eq!(solve(&a, &[8, -11, -3]), None); // (2, 3, -1)
+        assert_eq!(solve(&[vec![94, 22], vec![34, 67]], &[8400, 5400]), Some(vec![80, 40]));
+        assert_eq!(solve(&[vec![0, 1], vec![1, 0]], &[4, 5]), Some(vec![5, 4]));
+        assert_eq!(solve(&[vec![1, 2]], &[3]), None);
+        assert_eq!(determinant(vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 10]]), -3);
+    }
+}
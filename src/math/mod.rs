@@ -0,0 +1,200 @@
+pub mod linear;
+
+/// Integer types the number theory functions work on. Calculations happen in i128, so intermediate products of i64
+/// values cannot overflow; i128 products are reduced with overflow-safe multiplication where needed.
+pub trait Integer: Copy + Into<i128> + TryFrom<i128> {}
+
+impl Integer for i32 {}
+impl Integer for i64 {}
+impl Integer for i128 {}
+
+fn from_i128<T: Integer>(value: i128) -> Option<T> {
+    T::try_from(value).ok()
+}
+
+/// Greatest common divisor, always non-negative, gcd(0, 0) = 0. Panics if the result does not fit T, which is only
+/// the case for gcd(MIN, 0) and gcd(MIN, MIN).
+pub fn gcd<T: Integer>(a: T, b: T) -> T {
+    let (mut a, mut b) = (a.into().unsigned_abs(), b.into().unsigned_abs());
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    i128::try_from(a).ok().and_then(from_i128).expect("gcd does not fit the integer type")
+}
+
+/// Least common multiple, always non-negative, or None if it does not fit T
+pub fn lcm<T: Integer>(a: T, b: T) -> Option<T> {
+    let g: i128 = gcd(a, b).into();
+    if g == 0 {
+        return from_i128(0);
+    }
+    (a.into() / g).checked_mul(b.into())?.checked_abs().and_then(from_i128)
+}
+
+/// Extended Euclid: (g, x, y) such that a x + b y = g = gcd(a, b). Like `gcd`, panics only if g does not fit T.
+pub fn ext_gcd<T: Integer>(a: T, b: T) -> (T, T, T) {
+    let (g, x, y) = ext_gcd_i128(a.into(), b.into());
+    assert!(g >= 0, "gcd does not fit the integer type");
+    let convert = |v| from_i128(v).expect("Bezout coefficients are bounded by the inputs");
+    (convert(g), convert(x), convert(y))
+}
+
+// The steps only multiply and subtract, so doing them modulo 2^128 (wrapping) gives the exact result whenever that
+// fits i128. Only the coefficients of the last step, which are not returned, and quotients involving i128::MIN
+// actually wrap. The one result that does not fit is g = 2^127, for (MIN, 0) and (MIN, MIN), which comes out as MIN.
+fn ext_gcd_i128(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_s, mut s) = (1i128, 0i128);
+    let (mut old_t, mut t) = (0i128, 1i128);
+    while r != 0 {
+        let q = old_r.wrapping_div(r);
+        (old_r, r) = (r, old_r.wrapping_sub(q.wrapping_mul(r)));
+        (old_s, s) = (s, old_s.wrapping_sub(q.wrapping_mul(s)));
+        (old_t, t) = (t, old_t.wrapping_sub(q.wrapping_mul(t)));
+    }
+    if old_r < 0 && old_r != i128::MIN {
+        (-old_r, old_s.wrapping_neg(), old_t.wrapping_neg())
+    } else {
+        (old_r, old_s, old_t)
+    }
+}
+
+/// The x in 0..modulus with a x = 1 (mod modulus), None if a and modulus are not coprime or modulus is not positive
+pub fn mod_inv<T: Integer>(a: T, modulus: T) -> Option<T> {
+    let m: i128 = modulus.into();
+    if m <= 0 {
+        return None;
+    }
+    let (g, x, _) = ext_gcd_i128(a.into().rem_euclid(m), m);
+    if g != 1 {
+        return None;
+    }
+    from_i128(x.rem_euclid(m))
+}
+
+/// base^exp mod modulus by repeated squaring, in 0..modulus. Panics if modulus is not positive.
+pub fn mod_pow<T: Integer>(base: T, exp: u64, modulus: T) -> T {
+    let m: i128 = modulus.into();
+    assert!(m > 0, "modulus should be positive");
+    let mut result = 1 % m;
+    let mut base = base.into().rem_euclid(m);
+    let mut exp = exp;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = mul_mod(result, base, m);
+        }
+        base = mul_mod(base, base, m);
+        exp >>= 1;
+    }
+    from_i128(result).expect("smaller than the modulus")
+}
+
+/// a b mod m for a, b in 0..m, doubling instead of multiplying when the product would overflow
+fn mul_mod(a: i128, b: i128, m: i128) -> i128 {
+    if let Some(product) = a.checked_mul(b) {
+        return product % m;
+    }
+    let (a, mut b, m) = (a as u128, b as u128, m as u128);
+    let mut a = a;
+    let mut result: u128 = 0;
+    while b > 0 {
+        if b & 1 == 1 {
+            result = (result + a) % m; // both below m < 2^127, so the sum fits u128
+        }
+        a = (a + a) % m;
+        b >>= 1;
+    }
+    result as i128
+}
+
+/// Chinese remainder theorem: the smallest non-negative x with x = residue (mod modulus) for all congruences, and the
+/// combined modulus (the lcm of all moduli). Moduli need not be coprime. None if the congruences contradict each other,
+/// a modulus is not positive, or the combined modulus does not fit T.
+pub fn crt<T: Integer>(congruences: &[(T, T)]) -> Option<(T, T)> {
+    let mut x: i128 = 0;
+    let mut m: i128 = 1;
+    for &(residue, modulus) in congruences {
+        let (r, n): (i128, i128) = (residue.into(), modulus.into());
+        if n <= 0 {
+            return None;
+        }
+        // x + m k = r (mod n), so m k = r - x (mod n), solvable if gcd(m, n) divides r - x
+        let (g, p, _) = ext_gcd_i128(m, n);
+        let diff = (r - x).rem_euclid(n);
+        if diff % g != 0 {
+            return None;
+        }
+        let step = n / g;
+        let k = mul_mod(p.rem_euclid(step), diff / g % step, step);
+        let combined = m.checked_mul(step)?;
+        x = (x + mul_mod(m % combined, k, combined)) % combined;
+        m = combined;
+    }
+    Some((from_i128(x)?, from_i128(m)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gcd_lcm() {
+        assert_eq!(gcd(12i64, 18), 6);
+        assert_eq!(gcd(-12i64, 18), 6);
+        assert_eq!(gcd(0i64, 0), 0);
+        assert_eq!(lcm(4i64, 6), Some(12));
+        assert_eq!(lcm(101i64, 103), Some(10403));
+        assert_eq!(lcm(i64::MAX, i64::MAX - 1), None);
+        assert_eq!(lcm(i64::MAX as i128, i64::MAX as i128 - 1), Some(i64::MAX as i128 * (i64::MAX as i128 - 1)));
+    }
+
+    #[test]
+    fn test_ext_gcd_mod_inv() {
+        let (g, x, y) = ext_gcd(240i64, 46);
+        assert_eq!((g, 240 * x + 46 * y), (2, 2));
+        assert_eq!(mod_inv(3i64, 11), Some(4));
+        assert_eq!(mod_inv(-3i64, 11), Some(7));
+        assert_eq!(mod_inv(6i64, 9), None);
+        assert_eq!(mod_inv(101i64, 103), Some(51));
+    }
+
+    #[test]
+    fn test_ext_gcd_extremes() {
+        assert_eq!(ext_gcd(i128::MIN, -1), (1, 0, -1));
+        assert_eq!(ext_gcd(-1, i128::MIN), (1, -1, 0));
+        assert_eq!(ext_gcd(i128::MIN, i128::MAX), (1, -1, -1));
+        for b in [3, -3, i128::MAX - 1, i128::MIN + 1] {
+            let (g, x, y) = ext_gcd(i128::MIN, b);
+            // the exact sum fits, so checking it modulo 2^128 is enough
+            assert_eq!((g, i128::MIN.wrapping_mul(x).wrapping_add(b.wrapping_mul(y))), (gcd(i128::MIN, b), g));
+        }
+        assert_eq!(ext_gcd(i64::MIN, -1), (1, 0, -1));
+    }
+
+    #[test]
+    #[should_panic(expected = "gcd does not fit the integer type")]
+    fn test_ext_gcd_too_large() {
+        ext_gcd(i128::MIN, 0);
+    }
+
+    #[test]
+    fn test_mod_pow() {
+        assert_eq!(mod_pow(2i64, 10, 1000), 24);
+        assert_eq!(mod_pow(-2i64, 3, 5), 2);
+        assert_eq!(mod_pow(7i64, 0, 1), 0);
+        // modulo 2^127 - 1 powers of 2 repeat every 127 steps, and squaring along the way needs more than 128 bits
+        assert_eq!(mod_pow(2i128, 127, i128::MAX), 1);
+        assert_eq!(mod_pow(2i128, 1000, i128::MAX), 1 << (1000 % 127));
+        assert_eq!(mod_pow(i128::MAX - 1, 2, i128::MAX), 1);
+    }
+
+    #[test]
+    fn test_crt() {
+        assert_eq!(crt(&[(2i64, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        // the step at which robots are at positions 12 (mod 101) and 64 (mod 103)
+        assert_eq!(crt(&[(12i64, 101), (64, 103)]), Some((7789, 10403)));
+        assert_eq!(crt(&[(2i64, 4), (4, 6)]), Some((10, 12)));
+        assert_eq!(crt(&[(1i64, 4), (2, 6)]), None);
+        assert_eq!(crt::<i64>(&[]), Some((0, 1)));
+    }
+}
@@ -0,0 +1,67 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Caches the results of a recursive function. The function gets the memo itself as first argument, so that its
+/// recursive calls go through `Memo::get` and are cached as well:
+/// ```
+/// use aoc::memo::Memo;
+///
+/// let mut fibonacci = Memo::new(&|memo: &mut Memo<u64, u64>, n: &u64| {
+///     if *n < 2 { *n } else { memo.get(n - 1) + memo.get(n - 2) }
+/// });
+/// assert_eq!(fibonacci.get(90), 2880067194370816120);
+/// ```
+pub struct Memo<'f, K, V> {
+    cache: HashMap<K, V>,
+    f: &'f dyn Fn(&mut Memo<'f, K, V>, &K) -> V,
+}
+
+impl <'f, K, V> Memo<'f, K, V> where
+    K: Hash + Eq,
+    V: Clone,
+{
+    pub fn new(f: &'f dyn Fn(&mut Memo<'f, K, V>, &K) -> V) -> Self {
+        Memo { cache: HashMap::new(), f }
+    }
+
+    /// The cached value for `key`, calculated first if needed
+    pub fn get(&mut self, key: K) -> V {
+        if let Some(value) = self.cache.get(&key) {
+            return value.clone();
+        }
+        let f = self.f;
+        let value = f(self, &key);
+        self.cache.insert(key, value.clone());
+        value
+    }
+
+    /// The number of cached values
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    #[test]
+    fn test_memo_calls_once_per_key() {
+        let calls = Cell::new(0);
+        // number of paths from (0, 0) to (x, y) stepping right or down
+        let paths = |memo: &mut Memo<(u32, u32), u64>, &(x, y): &(u32, u32)| {
+            calls.set(calls.get() + 1);
+            if x == 0 || y == 0 { 1 } else { memo.get((x - 1, y)) + memo.get((x, y - 1)) }
+        };
+        let mut memo = Memo::new(&paths);
+        assert_eq!(memo.get((16, 16)), 601080390);
+        assert_eq!((calls.get(), memo.len()), (17 * 17 - 1, 17 * 17 - 1));
+        assert_eq!(memo.get((3, 3)), 20);
+        assert_eq!(calls.get(), 17 * 17 - 1);
+    }
+}
@@ -0,0 +1,227 @@
+//! Parser combinators for puzzle input formats. A parser is any `Fn(Input) -> ParseResult`, or a tuple of parsers
+//! which parses its elements in order. For example, a robot like `p=0,4 v=3,-3`:
+//! ```
+//! use aoc::parse::{int, literal, parse_all, preceded};
+//!
+//! let robot = (preceded(literal("p="), int()), preceded(literal(","), int()),
+//!              preceded(literal(" v="), int()), preceded(literal(","), int()));
+//! assert_eq!(parse_all(&robot, "p=0,4 v=3,-3"), Ok((0, 4, 3, -3)));
+//! assert_eq!(parse_all(&robot, "p=0,4 v=3;-3").unwrap_err().to_string(), "expected ',' at position 9");
+//! ```
+
+pub mod scan;
+
+use std::fmt;
+use std::str::FromStr;
+
+/// The text being parsed and the position (in bytes) up to which it has been parsed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Input<'a> {
+    text: &'a str,
+    position: usize,
+}
+
+impl <'a> Input<'a> {
+    pub fn new(text: &'a str) -> Self {
+        Input { text, position: 0 }
+    }
+
+    /// The part that is not parsed yet
+    pub fn rest(&self) -> &'a str {
+        &self.text[self.position..]
+    }
+
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    fn advance(self, bytes: usize) -> Self {
+        Input { text: self.text, position: self.position + bytes }
+    }
+
+    fn error<T>(&self, expected: impl Into<String>) -> ParseResult<'a, T> {
+        Err(Error { position: self.position, expected: expected.into() })
+    }
+}
+
+/// What was expected, and at which byte position of the text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Error {
+    pub position: usize,
+    pub expected: String,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "expected {} at position {}", self.expected, self.position)
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// The parsed value and the input after it
+pub type ParseResult<'a, T> = Result<(T, Input<'a>), Error>;
+
+/// Parsers work on any input, so one parser can be built once and used for every line
+pub trait Parser<T> {
+    fn parse<'a>(&self, input: Input<'a>) -> ParseResult<'a, T>;
+}
+
+impl <T, F> Parser<T> for F where
+    F: for<'a> Fn(Input<'a>) -> ParseResult<'a, T>,
+{
+    fn parse<'a>(&self, input: Input<'a>) -> ParseResult<'a, T> {
+        self(input)
+    }
+}
+
+macro_rules! tuple_parser {
+    ($($parser:ident $value:ident),+) => {
+        impl <$($value, $parser: Parser<$value>),+> Parser<($($value,)+)> for ($($parser,)+) {
+            #[allow(non_snake_case)]
+            fn parse<'a>(&self, input: Input<'a>) -> ParseResult<'a, ($($value,)+)> {
+                let ($($parser,)+) = self;
+                $(let ($value, input) = $parser.parse(input)?;)+
+                Ok((($($value,)+), input))
+            }
+        }
+    };
+}
+
+tuple_parser!(PA A, PB B);
+tuple_parser!(PA A, PB B, PC C);
+tuple_parser!(PA A, PB B, PC C, PD D);
+tuple_parser!(PA A, PB B, PC C, PD D, PE E);
+tuple_parser!(PA A, PB B, PC C, PD D, PE E, PF F);
+
+/// Runs `parser` on the whole of `text`, which should be fully consumed
+pub fn parse_all<T, P: Parser<T>>(parser: &P, text: &str) -> Result<T, Error> {
+    let (value, rest) = parser.parse(Input::new(text))?;
+    if rest.rest().is_empty() { Ok(value) } else { rest.error("end of input").map(|(value, _)| value) }
+}
+
+/// Exactly the text `expected`
+pub fn literal(expected: &'static str) -> impl for<'a> Fn(Input<'a>) -> ParseResult<'a, ()> {
+    move |input: Input<'_>| {
+        if input.rest().starts_with(expected) {
+            Ok(((), input.advance(expected.len())))
+        } else {
+            input.error(format!("'{}'", expected))
+        }
+    }
+}
+
+/// An integer with an optional `+` or `-` sign. Fails if the number does not fit T (or is negative for unsigned T).
+pub fn int<T: FromStr>() -> impl for<'a> Fn(Input<'a>) -> ParseResult<'a, T> {
+    move |input: Input<'_>| {
+        let rest = input.rest();
+        let sign = rest.starts_with(['+', '-']) as usize;
+        let digits = rest[sign..].bytes().take_while(u8::is_ascii_digit).count();
+        if digits == 0 {
+            return input.error("integer");
+        }
+        let number = rest[..sign + digits].strip_prefix('+').unwrap_or(&rest[..sign + digits]);
+        match number.parse() {
+            Ok(value) => Ok((value, input.advance(sign + digits))),
+            Err(_) => input.error(format!("integer of type {}", std::any::type_name::<T>())),
+        }
+    }
+}
+
+/// Zero or more whitespace characters
+pub fn ws() -> impl for<'a> Fn(Input<'a>) -> ParseResult<'a, ()> {
+    move |input: Input<'_>| {
+        let rest = input.rest();
+        Ok(((), input.advance(rest.len() - rest.trim_start().len())))
+    }
+}
+
+/// `parser` surrounded by optional whitespace
+pub fn spaced<T, P: Parser<T>>(parser: P) -> impl for<'a> Fn(Input<'a>) -> ParseResult<'a, T> {
+    let whitespace = ws();
+    move |input: Input<'_>| {
+        let (_, input) = whitespace(input)?;
+        let (value, input) = parser.parse(input)?;
+        let (_, input) = whitespace(input)?;
+        Ok((value, input))
+    }
+}
+
+/// The value of `parser` after `prefix`
+pub fn preceded<S, T, PS: Parser<S>, P: Parser<T>>(prefix: PS, parser: P) -> impl for<'a> Fn(Input<'a>) -> ParseResult<'a, T> {
+    move |input: Input<'_>| {
+        let (_, input) = prefix.parse(input)?;
+        parser.parse(input)
+    }
+}
+
+/// The value of `parser` before `suffix`
+pub fn terminated<S, T, P: Parser<T>, PS: Parser<S>>(parser: P, suffix: PS) -> impl for<'a> Fn(Input<'a>) -> ParseResult<'a, T> {
+    move |input: Input<'_>| {
+        let (value, input) = parser.parse(input)?;
+        let (_, input) = suffix.parse(input)?;
+        Ok((value, input))
+    }
+}
+
+/// One or more values of `parser` with `separator` in between
+pub fn separated<S, T, P: Parser<T>, PS: Parser<S>>(parser: P, separator: PS) -> impl for<'a> Fn(Input<'a>) -> ParseResult<'a, Vec<T>> {
+    move |input: Input<'_>| {
+        let (first, mut input) = parser.parse(input)?;
+        let mut values = vec![first];
+        while let Ok((_, after_separator)) = separator.parse(input) {
+            let (value, after_value) = parser.parse(after_separator)?;
+            values.push(value);
+            input = after_value;
+        }
+        Ok((values, input))
+    }
+}
+
+/// The value of `parser`, or None without consuming anything if it fails
+pub fn optional<T, P: Parser<T>>(parser: P) -> impl for<'a> Fn(Input<'a>) -> ParseResult<'a, Option<T>> {
+    move |input: Input<'_>| match parser.parse(input) {
+        Ok((value, rest)) => Ok((Some(value), rest)),
+        Err(_) => Ok((None, input)),
+    }
+}
+
+/// The value of `parser` transformed by `f`
+pub fn map<T, U, P: Parser<T>, F: Fn(T) -> U>(parser: P, f: F) -> impl for<'a> Fn(Input<'a>) -> ParseResult<'a, U> {
+    move |input: Input<'_>| parser.parse(input).map(|(value, rest)| (f(value), rest))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_int() {
+        assert_eq!(parse_all(&int::<i32>(), "-42"), Ok(-42));
+        assert_eq!(parse_all(&int::<u64>(), "+94"), Ok(94));
+        assert_eq!(parse_all(&int::<u8>(), "256"), Err(Error { position: 0, expected: String::from("integer of type u8") }));
+        assert_eq!(parse_all(&int::<u8>(), "-1").unwrap_err().position, 0);
+        assert_eq!(parse_all(&int::<i32>(), "12a"), Err(Error { position: 2, expected: String::from("end of input") }));
+        assert_eq!(parse_all(&int::<i32>(), "x").unwrap_err().expected, "integer");
+    }
+
+    #[test]
+    fn test_claw_machine_button() {
+        let button = (preceded(literal("Button A: X"), int::<u64>()), preceded(literal(", Y"), int::<u64>()));
+        assert_eq!(parse_all(&button, "Button A: X+94, Y+34"), Ok((94, 34)));
+        assert_eq!(parse_all(&button, "Button A: X+94,Y+34"), Err(Error { position: 14, expected: String::from("', Y'") }));
+    }
+
+    #[test]
+    fn test_separated_and_spaced() {
+        let equation = (terminated(int::<u64>(), literal(": ")), separated(int::<u64>(), literal(" ")));
+        assert_eq!(parse_all(&equation, "190: 10 19"), Ok((190, vec![10, 19])));
+
+        let list = separated(spaced(int::<i32>()), literal(","));
+        assert_eq!(parse_all(&list, " 1, 2 ,3 "), Ok(vec![1, 2, 3]));
+        assert_eq!(parse_all(&list, "1,2,").unwrap_err().position, 4);
+
+        let signed = (optional(literal("-")), map(int::<u32>(), |x| x * 2));
+        assert_eq!(parse_all(&signed, "-4"), Ok((Some(()), 8)));
+    }
+}
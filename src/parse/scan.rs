@@ -0,0 +1,92 @@
+use std::str::FromStr;
+
+use super::Error;
+
+/// Extracts typed values from a line, like `scanf`: every `{}` in the pattern is a field, everything else must match
+/// literally. Gives a `Result` with a tuple of the values (or the value itself for a single field), or the
+/// `parse::Error` pointing at the first mismatch.
+/// ```
+/// let line = "p=0,4 v=3,-3";
+/// assert_eq!(aoc::scan!("p={},{} v={},{}", line => i32, i32, i32, i32), Ok((0, 4, 3, -3)));
+/// assert_eq!(aoc::scan!("p={},{} v={},{}", "p=0,4 v=x,-3" => i32, i32, i32, i32).unwrap_err().position, 8);
+/// ```
+#[macro_export]
+macro_rules! scan {
+    ($pattern:expr, $input:expr => $t:ty $(,)?) => {
+        $crate::parse::scan::scan_fields($pattern, $input, 1)
+            .and_then(|fields| $crate::parse::scan::scan_field::<$t>(fields[0]))
+    };
+    ($pattern:expr, $input:expr => $($t:ty),+ $(,)?) => {
+        $crate::parse::scan::scan_fields($pattern, $input, [$(stringify!($t)),+].len()).and_then(|fields| {
+            let mut fields = fields.into_iter();
+            Ok(($($crate::parse::scan::scan_field::<$t>(fields.next().unwrap())?,)+))
+        })
+    };
+}
+
+/// The text and byte position of every field of `pattern` in `input`. A field extends up to the first occurrence of
+/// the literal text following it in the pattern, or to the end of the input for a field at the end of the pattern.
+/// Panics if the pattern does not have `field_count` fields, since that is a mistake in the pattern itself.
+pub fn scan_fields<'a>(pattern: &str, input: &'a str, field_count: usize) -> Result<Vec<(&'a str, usize)>, Error> {
+    let literals: Vec<&str> = pattern.split("{}").collect();
+    assert_eq!(literals.len() - 1, field_count, "pattern '{}' should have a field per type", pattern);
+
+    let mut fields = Vec::new();
+    let mut position = 0;
+    for (i, literal) in literals.iter().enumerate() {
+        if !input[position..].starts_with(literal) {
+            // in bytes, but counted per char so that it always ends on a char boundary
+            let matching: usize = input[position..].chars().zip(literal.chars())
+                .take_while(|(a, b)| a == b)
+                .map(|(a, _)| a.len_utf8())
+                .sum();
+            return Err(Error { position: position + matching, expected: format!("'{}'", &literal[matching..]) });
+        }
+        position += literal.len();
+        if i + 1 == literals.len() {
+            break;
+        }
+        let next = literals[i + 1];
+        let len = if next.is_empty() { input.len() - position } else {
+            input[position..].find(next).ok_or_else(|| Error { position: input.len(), expected: format!("'{}'", next) })?
+        };
+        fields.push((&input[position..position + len], position));
+        position += len;
+    }
+    if position < input.len() {
+        return Err(Error { position, expected: String::from("end of input") });
+    }
+    Ok(fields)
+}
+
+/// Parses a field found by `scan_fields` into T
+pub fn scan_field<T: FromStr>((text, position): (&str, usize)) -> Result<T, Error> {
+    text.parse().map_err(|_| Error { position, expected: std::any::type_name::<T>().to_string() })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scan() {
+        assert_eq!(crate::scan!("Button A: X+{}, Y+{}", "Button A: X+94, Y+34" => u64, u64), Ok((94, 34)));
+        assert_eq!(crate::scan!("{}: {}", "190: 10 19" => u64, String), Ok((190, String::from("10 19"))));
+        assert_eq!(crate::scan!("Prize: X={}, Y={}", "Prize: X=8400, Y=5400" => i64, i64), Ok((8400, 5400)));
+    }
+
+    #[test]
+    fn test_scan_errors() {
+        fn error<T>(position: usize, expected: &str) -> Result<T, Error> {
+            Err(Error { position, expected: String::from(expected) })
+        }
+        assert_eq!(crate::scan!("Button A: X+{}, Y+{}", "Button B: X+94, Y+34" => u64, u64), error(7, "'A: X+'"));
+        assert_eq!(crate::scan!("Button A: X+{}, Y+{}", "Button A: X+94 Y+34" => u64, u64), error(19, "', Y+'"));
+        assert_eq!(crate::scan!("Button A: X+{}, Y+{}", "Button A: X+9a, Y+34" => u64, u64), error(12, "u64"));
+        assert_eq!(crate::scan!("x={}!", "x=1!?" => u8), error(4, "end of input"));
+        // é and è share their first byte
+        assert_eq!(crate::scan!("é={}", "è=1" => u8), error(0, "'é='"));
+        assert_eq!(crate::scan!("aé={}", "aè=1" => u8), error(1, "'é='"));
+        assert_eq!(crate::scan!("x={}!", "x=1!" => u8), Ok(1));
+    }
+}
@@ -0,0 +1,282 @@
+use std::collections::HashSet;
+
+use crate::map::Mapp;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Heading {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Heading {
+    const ALL: [Heading; 4] = [Heading::Up, Heading::Right, Heading::Down, Heading::Left];
+
+    pub fn turn_right(&self) -> Self {
+        Heading::ALL[(*self as usize + 1) % 4]
+    }
+
+    pub fn offset(&self) -> (isize, isize) {
+        match self {
+            Heading::Up => (0, -1),
+            Heading::Right => (1, 0),
+            Heading::Down => (0, 1),
+            Heading::Left => (-1, 0),
+        }
+    }
+
+    fn from_char(c: char) -> Option<Self> {
+        match c {
+            '^' => Some(Heading::Up),
+            '>' => Some(Heading::Right),
+            'v' => Some(Heading::Down),
+            '<' => Some(Heading::Left),
+            _ => None,
+        }
+    }
+}
+
+/// A lab with obstacles (`#`) and a guard (`^`, `>`, `v` or `<`) who walks straight ahead and turns right whenever
+/// an obstacle is in front, until walking off the map.
+/// Cells are numbered `y * width + x`. For every cell and heading a jump table holds the cell where the guard would
+/// stop in front of the next obstacle, so a walk costs one lookup per turn instead of one per step.
+pub struct Lab {
+    width: usize,
+    height: usize,
+    obstacles: Vec<bool>,
+    start: (usize, Heading),
+    /// Per cell and heading: the cell before the next obstacle, None if there is no obstacle up to the edge
+    stops: Vec<[Option<usize>; 4]>,
+}
+
+impl Lab {
+    /// None if there is no guard on the map
+    pub fn new(map: &Mapp<char>) -> Option<Self> {
+        let (width, height) = (map.width(), map.height());
+        let guard = map.find(|&c| Heading::from_char(c).is_some())?;
+        let heading = Heading::from_char(*map.get(guard.0, guard.1)?)?;
+        let obstacles = (0..height).flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| map.get(x, y) == Some(&'#'))
+            .collect();
+        let mut lab = Lab { width, height, obstacles, start: (guard.1 * width + guard.0, heading), stops: Vec::new() };
+        lab.fill_stops();
+        Some(lab)
+    }
+
+    fn fill_stops(&mut self) {
+        let (width, height) = (self.width, self.height);
+        self.stops = vec![[None; 4]; width * height];
+        // each line of cells is visited starting at the edge the guard walks towards
+        for x in 0..width {
+            self.fill_line(Heading::Up, (0..height).map(|y| y * width + x));
+            self.fill_line(Heading::Down, (0..height).rev().map(|y| y * width + x));
+        }
+        for y in 0..height {
+            self.fill_line(Heading::Left, (0..width).map(|x| y * width + x));
+            self.fill_line(Heading::Right, (0..width).rev().map(|x| y * width + x));
+        }
+    }
+
+    fn fill_line(&mut self, heading: Heading, line: impl Iterator<Item = usize>) {
+        let mut stop = None;
+        let mut after_obstacle = false;
+        for cell in line {
+            if self.obstacles[cell] {
+                after_obstacle = true;
+            } else {
+                if after_obstacle {
+                    stop = Some(cell);
+                    after_obstacle = false;
+                }
+                self.stops[cell][heading as usize] = stop;
+            }
+        }
+    }
+
+    fn position(&self, cell: usize) -> (usize, usize) {
+        (cell % self.width, cell / self.width)
+    }
+
+    /// The cell next to `cell` in the direction of `heading`, None if that is off the map
+    fn ahead(&self, cell: usize, heading: Heading) -> Option<usize> {
+        let (x, y) = self.position(cell);
+        let (dx, dy) = heading.offset();
+        let (x, y) = (x.checked_add_signed(dx)?, y.checked_add_signed(dy)?);
+        (x < self.width && y < self.height).then_some(y * self.width + x)
+    }
+
+    /// The number of steps from `from` to `to` walking in the direction of `heading`, None if `to` is not ahead
+    fn steps_to(&self, from: usize, to: usize, heading: Heading) -> Option<usize> {
+        let ((fx, fy), (tx, ty)) = (self.position(from), self.position(to));
+        match heading {
+            Heading::Up if fx == tx => fy.checked_sub(ty),
+            Heading::Down if fx == tx => ty.checked_sub(fy),
+            Heading::Left if fy == ty => fx.checked_sub(tx),
+            Heading::Right if fy == ty => tx.checked_sub(fx),
+            _ => None,
+        }
+    }
+
+    /// The cell where the guard stops in front of an obstacle, None if the guard walks off the map
+    fn jump(&self, cell: usize, heading: Heading, extra_obstacle: Option<usize>) -> Option<usize> {
+        let stop = self.stops[cell][heading as usize];
+        let Some(extra) = extra_obstacle else { return stop };
+        let Some(extra_steps) = self.steps_to(cell, extra, heading).filter(|&steps| steps > 0) else { return stop };
+        let stop_steps = stop.map_or(usize::MAX, |stop| self.steps_to(cell, stop, heading).unwrap());
+        if extra_steps <= stop_steps {
+            let (x, y) = self.position(cell);
+            let (dx, dy) = heading.offset();
+            let steps = extra_steps as isize - 1;
+            Some((y as isize + dy * steps) as usize * self.width + (x as isize + dx * steps) as usize)
+        } else {
+            stop
+        }
+    }
+
+    /// Whether the guard, walking from `cell` in the direction of `heading`, ends up walking in circles
+    fn loops(&self, cell: usize, heading: Heading, extra_obstacle: Option<usize>) -> bool {
+        let (mut cell, mut heading) = (cell, heading);
+        let mut stops = HashSet::new();
+        while let Some(stop) = self.jump(cell, heading, extra_obstacle) {
+            if !stops.insert((stop, heading)) {
+                return true;
+            }
+            (cell, heading) = (stop, heading.turn_right());
+        }
+        false
+    }
+
+    /// The states of the guard one step at a time, from the start until the last cell before walking off the map.
+    /// Turning in place is a step too. None if the guard walks in circles, i.e. a state repeats.
+    fn walk(&self) -> Option<Vec<(usize, Heading)>> {
+        let mut seen = vec![false; 4 * self.obstacles.len()];
+        let mut states = Vec::new();
+        let (mut cell, mut heading) = self.start;
+        loop {
+            if std::mem::replace(&mut seen[4 * cell + heading as usize], true) {
+                return None;
+            }
+            states.push((cell, heading));
+            match self.ahead(cell, heading) {
+                None => return Some(states),
+                Some(next) if self.obstacles[next] => heading = heading.turn_right(),
+                Some(next) => cell = next,
+            }
+        }
+    }
+
+    /// The distinct positions the guard visits, in order of the first visit. None if the guard walks in circles.
+    pub fn patrol(&self) -> Option<Vec<(usize, usize)>> {
+        let mut visited = vec![false; self.obstacles.len()];
+        Some(self.walk()?.into_iter()
+            .filter(|&(cell, _)| !std::mem::replace(&mut visited[cell], true))
+            .map(|(cell, _)| self.position(cell))
+            .collect())
+    }
+
+    /// Whether the guard walks in circles, with an optional extra obstacle at (x, y)
+    pub fn is_loop(&self, extra_obstacle: Option<(usize, usize)>) -> bool {
+        let extra = extra_obstacle.map(|(x, y)| y * self.width + x);
+        if extra == Some(self.start.0) {
+            return false; // cannot put an obstacle on the guard
+        }
+        self.loops(self.start.0, self.start.1, extra)
+    }
+
+    /// The positions where a single extra obstacle makes the guard walk in circles.
+    /// Only cells on the original patrol matter, as the guard never meets an obstacle elsewhere. An obstacle is tried
+    /// when the guard is about to enter its cell for the first time: up to there the walk is the same as without it,
+    /// so the loop check can start right in front of it instead of at the start.
+    /// None if the guard already walks in circles without an extra obstacle.
+    pub fn loop_obstructions(&self) -> Option<Vec<(usize, usize)>> {
+        let mut visited = vec![false; self.obstacles.len()];
+        visited[self.start.0] = true;
+        let mut obstructions = Vec::new();
+        for (cell, heading) in self.walk()? {
+            let Some(next) = self.ahead(cell, heading) else { continue };
+            if self.obstacles[next] || std::mem::replace(&mut visited[next], true) {
+                continue;
+            }
+            if self.loops(cell, heading, Some(next)) {
+                obstructions.push(self.position(next));
+            }
+        }
+        Some(obstructions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::map_from;
+
+    fn lab(input: &str) -> Lab {
+        Lab::new(&map_from(input)).unwrap()
+    }
+
+    const INPUT: &str = "
+        ....#.....
+        .........#
+        ..........
+        ..#.......
+        .......#..
+        ..........
+        .#..^.....
+        ........#.
+        #.........
+        ......#...
+    ";
+
+    #[test]
+    fn test_patrol() {
+        let lab = lab(INPUT);
+        let patrol = lab.patrol().unwrap();
+        assert_eq!(patrol.len(), 41);
+        assert_eq!((patrol[0], patrol[40]), ((4, 6), (7, 9)));
+        assert!(!lab.is_loop(None));
+    }
+
+    #[test]
+    fn test_loop_obstructions() {
+        let lab = lab(INPUT);
+        let mut obstructions = lab.loop_obstructions().unwrap();
+        obstructions.sort();
+        assert_eq!(obstructions, vec![(1, 8), (3, 6), (3, 8), (6, 7), (7, 7), (7, 9)]);
+        assert!(obstructions.iter().all(|&obstruction| lab.is_loop(Some(obstruction))));
+        assert!(!lab.is_loop(Some((4, 6))));
+        assert!(!lab.is_loop(Some((0, 0))));
+    }
+
+    #[test]
+    fn test_start_heading() {
+        let lab = lab("
+            .#.
+            .>#
+            ...
+        ");
+        assert_eq!(lab.patrol(), Some(vec![(1, 1), (1, 2)]));
+    }
+
+    #[test]
+    fn test_walking_in_circles() {
+        let circles = lab("
+            .#..
+            ...#
+            #^..
+            ..#.
+        ");
+        assert!(circles.is_loop(None));
+        assert_eq!(circles.patrol(), None);
+        assert_eq!(circles.loop_obstructions(), None);
+
+        // boxed in, so only turning
+        let boxed_in = lab("
+            .#.
+            #^#
+            .#.
+        ");
+        assert!(boxed_in.is_loop(None));
+        assert_eq!(boxed_in.patrol(), None);
+    }
+}
@@ -0,0 +1,148 @@
+use std::collections::BTreeMap;
+
+use crate::map::{Mapp, Neighbours};
+
+/// A race track: a single path without branches from `S` to `E` through a map of walls (`#`).
+/// Every track position is indexed by the number of picoseconds it takes to get there from the start, so the time a
+/// cheat saves is a difference of two indices minus the length of the cheat.
+pub struct RaceTrack {
+    width: usize,
+    path: Vec<(usize, usize)>,
+    /// Per map cell (`y * width + x`): the index of the cell on the path, None for walls
+    index: Vec<Option<usize>>,
+}
+
+impl RaceTrack {
+    /// None if the map lacks a start or end, or if the track from the start branches or dead ends before the end
+    pub fn new(map: &Mapp<char>) -> Option<Self> {
+        let start = map.find(|&c| c == 'S')?;
+        let end = map.find(|&c| c == 'E')?;
+        let width = map.width();
+        let mut index = vec![None; width * map.height()];
+        let mut path = vec![start];
+        index[start.1 * width + start.0] = Some(0);
+        let mut current = start;
+        while current != end {
+            let mut next = map.neighbours(current.0, current.1, Neighbours::Dir4)
+                .filter(|&(x, y)| map.get(x, y) != Some(&'#') && index[y * width + x].is_none());
+            let step = next.next()?;
+            if next.next().is_some() {
+                return None;
+            }
+            index[step.1 * width + step.0] = Some(path.len());
+            path.push(step);
+            current = step;
+        }
+        Some(RaceTrack { width, path, index })
+    }
+
+    /// The positions from start to end
+    pub fn path(&self) -> &[(usize, usize)] {
+        &self.path
+    }
+
+    /// The number of picoseconds to finish without cheating
+    pub fn time(&self) -> usize {
+        self.path.len() - 1
+    }
+
+    /// Calls `f` with the time saved by every cheat of at most `max_length` picoseconds that saves any time.
+    /// The cheats from a position end within a diamond around it, which is looked up cell by cell in the index grid.
+    fn for_each_cheat<F>(&self, max_length: usize, mut f: F) where
+        F: FnMut(usize),
+    {
+        let height = self.index.len() / self.width;
+        let max = max_length as isize;
+        for (from, &(x, y)) in self.path.iter().enumerate() {
+            for dy in -max..=max {
+                let Some(ty) = y.checked_add_signed(dy).filter(|&ty| ty < height) else { continue };
+                let reach = max - dy.abs();
+                for dx in -reach..=reach {
+                    let Some(tx) = x.checked_add_signed(dx).filter(|&tx| tx < self.width) else { continue };
+                    let Some(to) = self.index[ty * self.width + tx] else { continue };
+                    let length = (dx.abs() + dy.abs()) as usize;
+                    if let Some(saved) = to.checked_sub(from + length).filter(|&saved| saved > 0) {
+                        f(saved);
+                    }
+                }
+            }
+        }
+    }
+
+    /// The number of cheats of at most `max_length` picoseconds that save at least `min_saved` picoseconds
+    pub fn count_cheats(&self, max_length: usize, min_saved: usize) -> usize {
+        let mut count = 0;
+        self.for_each_cheat(max_length, |saved| if saved >= min_saved { count += 1 });
+        count
+    }
+
+    /// The number of cheats of at most `max_length` picoseconds per number of picoseconds saved
+    pub fn savings(&self, max_length: usize) -> BTreeMap<usize, usize> {
+        let mut histogram = BTreeMap::new();
+        self.for_each_cheat(max_length, |saved| *histogram.entry(saved).or_default() += 1);
+        histogram
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::map_from;
+
+    fn track(input: &str) -> Option<RaceTrack> {
+        RaceTrack::new(&map_from(input))
+    }
+
+    const INPUT: &str = "
+        ###############
+        #...#...#.....#
+        #.#.#.#.#.###.#
+        #S#...#.#.#...#
+        #######.#.#.###
+        #######.#.#...#
+        #######.#.###.#
+        ###..E#...#...#
+        ###.#######.###
+        #...###...#...#
+        #.#####.#.###.#
+        #.#...#.#.#...#
+        #.#.#.#.#.#.###
+        #...#...#...###
+        ###############
+    ";
+
+    #[test]
+    fn test_path() {
+        let track = track(INPUT).unwrap();
+        assert_eq!(track.time(), 84);
+        assert_eq!((track.path()[0], track.path()[84]), ((1, 3), (5, 7)));
+    }
+
+    #[test]
+    fn test_savings() {
+        let track = track(INPUT).unwrap();
+        let expected = [(2, 14), (4, 14), (6, 2), (8, 4), (10, 2), (12, 3), (20, 1), (36, 1), (38, 1), (40, 1), (64, 1)];
+        assert_eq!(track.savings(2), BTreeMap::from(expected));
+        assert_eq!(track.count_cheats(2, 12), 8);
+
+        let savings = track.savings(20);
+        assert_eq!((savings[&50], savings[&76]), (32, 3));
+        assert_eq!(track.count_cheats(20, 50), 285);
+    }
+
+    #[test]
+    fn test_not_a_single_track() {
+        assert!(track("
+            #####
+            #S..#
+            #.#.#
+            #..E#
+            #####
+        ").is_none());
+        assert!(track("
+            #####
+            #S#E#
+            #####
+        ").is_none());
+    }
+}
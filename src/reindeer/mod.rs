@@ -0,0 +1,119 @@
+use std::collections::HashSet;
+
+use crate::graph::search::{shortest_paths, ShortestPaths};
+use crate::map::{Mapp, Neighbours};
+
+pub const STEP_COST: usize = 1;
+pub const TURN_COST: usize = 1000;
+
+/// A position and the index of the direction faced in `Neighbours::Dir4.offsets()`, which turn clockwise from north
+type State = ((usize, usize), usize);
+
+const EAST: usize = 1;
+
+/// A maze with walls (`#`), a start tile (`S`) and an end tile (`E`). The reindeer starts facing east, and can step
+/// forward or turn 90 degrees in place.
+pub struct ReindeerMaze {
+    map: Mapp<char>,
+    start: (usize, usize),
+    end: (usize, usize),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Solution {
+    /// The lowest possible score to reach the end
+    pub score: usize,
+    /// The tiles on any path with the lowest score
+    pub best_tiles: HashSet<(usize, usize)>,
+}
+
+impl ReindeerMaze {
+    /// None if the map lacks a start or end tile
+    pub fn new(map: Mapp<char>) -> Option<Self> {
+        let start = map.find(|&c| c == 'S')?;
+        let end = map.find(|&c| c == 'E')?;
+        Some(ReindeerMaze { map, start, end })
+    }
+
+    fn successors(&self, &((x, y), dir): &State) -> Vec<(State, usize)> {
+        let mut next = vec![(((x, y), (dir + 1) % 4), TURN_COST), (((x, y), (dir + 3) % 4), TURN_COST)];
+        let (dx, dy) = Neighbours::Dir4.offsets()[dir];
+        if let (Some(ax), Some(ay)) = (x.checked_add_signed(dx), y.checked_add_signed(dy)) {
+            if self.map.get(ax, ay).is_some_and(|&c| c != '#') {
+                next.push((((ax, ay), dir), STEP_COST));
+            }
+        }
+        next
+    }
+
+    fn search(&self) -> ShortestPaths<State> {
+        shortest_paths([(self.start, EAST)], |state| self.successors(state))
+    }
+
+    fn end_states(&self) -> Vec<State> {
+        (0..4).map(|dir| (self.end, dir)).collect()
+    }
+
+    /// The lowest score to reach the end, None if it cannot be reached
+    pub fn lowest_score(&self) -> Option<usize> {
+        self.search().nearest(&self.end_states()).map(|(_, score)| score)
+    }
+
+    /// The lowest score and the tiles on all paths having that score, None if the end cannot be reached
+    pub fn solve(&self) -> Option<Solution> {
+        let paths = self.search();
+        let end_states = self.end_states();
+        let (_, score) = paths.nearest(&end_states)?;
+        let best_tiles = paths.on_shortest_paths(&end_states).into_iter().map(|(position, _)| position).collect();
+        Some(Solution { score, best_tiles })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::map_from;
+
+    fn maze(input: &str) -> ReindeerMaze {
+        ReindeerMaze::new(map_from(input)).unwrap()
+    }
+
+    #[test]
+    fn test_solve() {
+        let maze = maze("
+            #######
+            #....E#
+            #.#.#.#
+            #S....#
+            #######
+        ");
+        // east along the bottom and then north takes a single turn, any other route takes at least two
+        let solution = maze.solve().unwrap();
+        assert_eq!(solution.score, 4 + 1000 + 2);
+        assert_eq!(solution.best_tiles.len(), 7);
+        assert!(solution.best_tiles.contains(&(5, 2)) && !solution.best_tiles.contains(&(1, 1)));
+        assert_eq!(maze.lowest_score(), Some(1006));
+    }
+
+    #[test]
+    fn test_turn_around() {
+        // the only way is west, which takes two turns
+        let maze = maze("
+            #####
+            #E.S#
+            #####
+        ");
+        assert_eq!(maze.lowest_score(), Some(2002));
+        assert_eq!(maze.solve().unwrap().best_tiles.len(), 3);
+    }
+
+    #[test]
+    fn test_unreachable() {
+        let maze = maze("
+            #####
+            #E#S#
+            #####
+        ");
+        assert_eq!(maze.solve(), None);
+    }
+}
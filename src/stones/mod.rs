@@ -0,0 +1,128 @@
+use std::collections::HashSet;
+
+use crate::collections::Counter;
+use crate::memo::Memo;
+
+/// The number of decimal digits of `n`, 1 for 0
+pub fn digits(n: u64) -> u32 {
+    n.checked_ilog10().unwrap_or(0) + 1
+}
+
+/// The left and right half of the digits of `n` if it has an even number of them, e.g. 1000 gives (10, 0)
+pub fn split_digits(n: u64) -> Option<(u64, u64)> {
+    let len = digits(n);
+    if len % 2 == 1 {
+        return None;
+    }
+    let half = 10u64.pow(len / 2);
+    Some((n / half, n % half))
+}
+
+/// The rules of the puzzle: 0 becomes 1, a stone with an even number of digits splits in two halves and any other
+/// stone is multiplied by 2024
+pub fn blink(stone: u64) -> Vec<u64> {
+    if stone == 0 {
+        vec![1]
+    } else if let Some((left, right)) = split_digits(stone) {
+        vec![left, right]
+    } else {
+        vec![stone * 2024]
+    }
+}
+
+/// A row of stones that all change at once on every blink, according to a rule mapping a stone to its successors.
+/// The order of the stones never matters for the rules, so only the number of stones per value is tracked.
+pub struct Stones<R> {
+    rule: R,
+}
+
+impl <R, I> Stones<R> where
+    R: Fn(u64) -> I,
+    I: IntoIterator<Item = u64>,
+{
+    pub fn new(rule: R) -> Self {
+        Stones { rule }
+    }
+
+    /// The number of stones per value after one blink
+    pub fn step(&self, counts: &Counter<u64>) -> Counter<u64> {
+        let mut next = Counter::new();
+        for (&stone, count) in counts.iter() {
+            for successor in (self.rule)(stone) {
+                next.add_n(successor, count);
+            }
+        }
+        next
+    }
+
+    /// The number of stones per value after a number of blinks
+    pub fn evolve(&self, stones: &[u64], blinks: usize) -> Counter<u64> {
+        (0..blinks).fold(stones.iter().copied().collect(), |counts, _| self.step(&counts))
+    }
+
+    /// The number of stones after a number of blinks
+    pub fn count(&self, stones: &[u64], blinks: usize) -> usize {
+        self.evolve(stones, blinks).total()
+    }
+
+    /// As `count`, but recursing per stone and caching the count per (stone, blinks left). Faster than `count` when
+    /// only few of the values reached are ever needed at a given depth.
+    pub fn count_memoised(&self, stones: &[u64], blinks: usize) -> usize {
+        let count = |memo: &mut Memo<(u64, usize), usize>, &(stone, blinks): &(u64, usize)| -> usize {
+            if blinks == 0 {
+                return 1;
+            }
+            (self.rule)(stone).into_iter().map(|successor| memo.get((successor, blinks - 1))).sum()
+        };
+        let mut memo = Memo::new(&count);
+        stones.iter().map(|&stone| memo.get((stone, blinks))).sum()
+    }
+
+    /// The number of different values seen on any stone in the first `blinks` blinks, including the initial stones
+    pub fn distinct_values(&self, stones: &[u64], blinks: usize) -> usize {
+        let mut seen: HashSet<u64> = stones.iter().copied().collect();
+        let mut current = seen.clone();
+        for _ in 0..blinks {
+            current = current.into_iter()
+                .flat_map(|stone| (self.rule)(stone))
+                .filter(|&stone| seen.insert(stone))
+                .collect();
+        }
+        seen.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_digits() {
+        assert_eq!((digits(0), digits(9), digits(10), digits(u64::MAX)), (1, 1, 2, 20));
+        assert_eq!(split_digits(1000), Some((10, 0)));
+        assert_eq!(split_digits(253000), Some((253, 0)));
+        assert_eq!(split_digits(100), None);
+    }
+
+    #[test]
+    fn test_count() {
+        let stones = Stones::new(blink);
+        assert_eq!(stones.count(&[125, 17], 6), 22);
+        assert_eq!(stones.count(&[125, 17], 25), 55312);
+        assert_eq!(stones.count_memoised(&[125, 17], 25), 55312);
+        assert_eq!(stones.count(&[125, 17], 0), 2);
+        let counts = stones.evolve(&[125, 17], 2);
+        assert_eq!((counts.get(&0), counts.get(&2024), counts.get(&253), counts.len()), (1, 1, 1, 4));
+    }
+
+    #[test]
+    fn test_distinct_values() {
+        // 125 17 -> 253000 1 7 -> 253 0 2024 14168 -> 512072 1 20 24 28676032, where only the 1 was seen before
+        let stones = Stones::new(blink);
+        assert_eq!(stones.distinct_values(&[125, 17], 3), 13);
+        // a rule that cycles through a fixed set of values
+        let stones = Stones::new(|stone| [(stone + 1) % 5, (stone + 2) % 5]);
+        assert_eq!(stones.distinct_values(&[0], 100), 5);
+        assert_eq!(stones.count(&[0], 10), 1024);
+    }
+}
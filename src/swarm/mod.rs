@@ -0,0 +1,161 @@
+use crate::math::crt;
+
+/// A robot moving with constant velocity on a map that wraps around at the edges
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Robot {
+    pub position: (i64, i64),
+    pub velocity: (i64, i64),
+}
+
+impl Robot {
+    /// The position after `t` steps, computed directly instead of stepping
+    pub fn position_at(&self, t: i64, size: (i64, i64)) -> (i64, i64) {
+        (
+            (self.position.0 + self.velocity.0 * t).rem_euclid(size.0),
+            (self.position.1 + self.velocity.1 * t).rem_euclid(size.1),
+        )
+    }
+}
+
+/// Robots on a map of `size` (width, height)
+pub struct Swarm {
+    pub robots: Vec<Robot>,
+    pub size: (i64, i64),
+}
+
+impl Swarm {
+    pub fn new(robots: Vec<Robot>, size: (i64, i64)) -> Self {
+        Swarm { robots, size }
+    }
+
+    pub fn positions_at(&self, t: i64) -> Vec<(i64, i64)> {
+        self.robots.iter().map(|r| r.position_at(t, self.size)).collect()
+    }
+
+    /// Robot counts per quadrant (top left, top right, bottom left, bottom right), robots on the middle lines count
+    /// for none
+    pub fn quadrant_counts(&self, t: i64) -> [usize; 4] {
+        let middle = ((self.size.0 - 1) / 2, (self.size.1 - 1) / 2);
+        let mut counts = [0; 4];
+        for (x, y) in self.positions_at(t) {
+            if x != middle.0 && y != middle.1 {
+                counts[(x > middle.0) as usize + 2 * (y > middle.1) as usize] += 1;
+            }
+        }
+        counts
+    }
+
+    /// The product of the quadrant counts
+    pub fn safety_factor(&self, t: i64) -> usize {
+        self.quadrant_counts(t).iter().product()
+    }
+
+    /// Variance of the x and of the y coordinates, low when the robots are clustered
+    pub fn variance_at(&self, t: i64) -> (f64, f64) {
+        let positions = self.positions_at(t);
+        let variance = |values: Vec<f64>| {
+            let mean = values.iter().sum::<f64>() / values.len() as f64;
+            values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / values.len() as f64
+        };
+        (
+            variance(positions.iter().map(|p| p.0 as f64).collect()),
+            variance(positions.iter().map(|p| p.1 as f64).collect()),
+        )
+    }
+
+    /// Shannon entropy (in bits) of the robot distribution over square blocks of `block` cells wide, low when the
+    /// robots are concentrated in a few blocks
+    pub fn entropy_at(&self, t: i64, block: i64) -> f64 {
+        let columns = (self.size.0 + block - 1) / block;
+        let mut counts = vec![0usize; (columns * ((self.size.1 + block - 1) / block)) as usize];
+        for (x, y) in self.positions_at(t) {
+            counts[(y / block * columns + x / block) as usize] += 1;
+        }
+        let total = self.robots.len() as f64;
+        counts.iter().filter(|c| **c > 0).map(|&c| c as f64 / total).map(|p| -p * p.log2()).sum()
+    }
+
+    /// The longest number of horizontally adjacent occupied cells, long for pictures with straight lines
+    pub fn longest_run_at(&self, t: i64) -> usize {
+        let mut positions = self.positions_at(t);
+        positions.sort_by_key(|&(x, y)| (y, x));
+        positions.dedup();
+        let mut longest = 0;
+        let mut run = 0;
+        let mut previous: Option<(i64, i64)> = None;
+        for (x, y) in positions {
+            run = match previous {
+                Some((px, py)) if py == y && px + 1 == x => run + 1,
+                _ => 1,
+            };
+            longest = longest.max(run);
+            previous = Some((x, y));
+        }
+        longest
+    }
+
+    /// The first step in which the robots form a picture, i.e. are clustered the most. The x coordinates repeat every
+    /// width steps and the y coordinates every height steps, so the step with minimum x variance within the first
+    /// width steps and the one with minimum y variance within the first height steps are combined using the Chinese
+    /// remainder theorem. None if the map size is not positive or the steps contradict each other.
+    pub fn find_picture(&self) -> Option<i64> {
+        let most_clustered = |period: i64, axis: fn((f64, f64)) -> f64| {
+            (0..period).min_by(|&a, &b| axis(self.variance_at(a)).total_cmp(&axis(self.variance_at(b))))
+        };
+        let t_x = most_clustered(self.size.0, |v| v.0)?;
+        let t_y = most_clustered(self.size.1, |v| v.1)?;
+        crt(&[(t_x, self.size.0), (t_y, self.size.1)]).map(|(t, _)| t)
+    }
+
+    /// The first step up to and including `max_t` for which `is_picture` is true
+    pub fn first_frame_where<P>(&self, max_t: i64, is_picture: P) -> Option<i64> where
+        P: Fn(&Swarm, i64) -> bool,
+    {
+        (0..=max_t).find(|&t| is_picture(self, t))
+    }
+
+    /// The map at step `t`, `#` for cells with robots and `.` for empty cells
+    pub fn render_at(&self, t: i64) -> String {
+        let mut cells = vec![vec!['.'; self.size.0 as usize]; self.size.1 as usize];
+        for (x, y) in self.positions_at(t) {
+            cells[y as usize][x as usize] = '#';
+        }
+        cells.into_iter().map(|row| row.into_iter().chain(['\n']).collect::<String>()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A 3x3 block centered at (5, 3) at step 40, with velocities that scatter it at any other step
+    fn picture_swarm() -> Swarm {
+        let size = (11, 7);
+        let velocities = [(1, 2), (2, 3), (3, 4), (4, 5), (5, 6), (6, 1), (7, 2), (8, 3), (9, 5)];
+        let robots = velocities.iter().enumerate().map(|(i, &velocity)| {
+            let target = (4 + i as i64 % 3, 2 + i as i64 / 3);
+            let scattered = Robot { position: target, velocity };
+            Robot { position: scattered.position_at(-40, size), velocity }
+        }).collect();
+        Swarm::new(robots, size)
+    }
+
+    #[test]
+    fn test_position_at() {
+        let robot = Robot { position: (2, 4), velocity: (2, -3) };
+        assert_eq!(robot.position_at(5, (11, 7)), (1, 3));
+        assert_eq!(robot.position_at(77 * 1000, (11, 7)), (2, 4));
+    }
+
+    #[test]
+    fn test_find_picture() {
+        let swarm = picture_swarm();
+        assert_eq!(swarm.find_picture(), Some(40));
+        assert_eq!(swarm.render_at(40), "...........\n...........\n....###....\n....###....\n....###....\n...........\n...........\n");
+        assert_eq!(swarm.first_frame_where(100, |s, t| s.variance_at(t).0 + s.variance_at(t).1 < 2.0), Some(40));
+        assert_eq!(swarm.longest_run_at(40), 3);
+        assert!(swarm.entropy_at(40, 3) < swarm.entropy_at(41, 3));
+        assert_eq!(swarm.quadrant_counts(40), [1, 1, 1, 1]);
+        assert_eq!(swarm.safety_factor(40), 1);
+    }
+}
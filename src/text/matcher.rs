@@ -0,0 +1,173 @@
+use std::collections::{HashMap, VecDeque};
+
+/// An occurrence of pattern number `pattern` at the bytes `start..end` of a text
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Match {
+    pub pattern: usize,
+    pub start: usize,
+    pub end: usize,
+}
+
+/// Byte-wise prefix tree of patterns. Patterns are identified by their index in the slice they were built from.
+pub struct Trie {
+    children: Vec<HashMap<u8, usize>>,
+    // patterns ending at each node, more than one if patterns are duplicated
+    ends: Vec<Vec<usize>>,
+    lengths: Vec<usize>,
+}
+
+impl Trie {
+    pub fn new<P: AsRef<[u8]>>(patterns: &[P]) -> Self {
+        let mut trie = Trie { children: vec![HashMap::new()], ends: vec![Vec::new()], lengths: Vec::new() };
+        for (id, pattern) in patterns.iter().enumerate() {
+            let mut node = 0;
+            for &byte in pattern.as_ref() {
+                node = match trie.children[node].get(&byte) {
+                    Some(&next) => next,
+                    None => {
+                        trie.children.push(HashMap::new());
+                        trie.ends.push(Vec::new());
+                        let next = trie.children.len() - 1;
+                        trie.children[node].insert(byte, next);
+                        next
+                    },
+                };
+            }
+            trie.ends[node].push(id);
+            trie.lengths.push(pattern.as_ref().len());
+        }
+        trie
+    }
+
+    /// The length of pattern number `pattern`
+    pub fn pattern_len(&self, pattern: usize) -> usize {
+        self.lengths[pattern]
+    }
+
+    /// All patterns starting at `start` in `text`, shortest first. Takes time in the order of the longest pattern.
+    pub fn matches_at(&self, text: &[u8], start: usize) -> Vec<Match> {
+        let mut matches = Vec::new();
+        let mut node = 0;
+        for end in start..=text.len() {
+            matches.extend(self.ends[node].iter().map(|&pattern| Match { pattern, start, end }));
+            match text.get(end).and_then(|byte| self.children[node].get(byte)) {
+                Some(&next) => node = next,
+                None => break,
+            }
+        }
+        matches
+    }
+}
+
+/// Aho–Corasick automaton: finds all (possibly overlapping) occurrences of all patterns in a single pass over the
+/// text, in time linear in the text length plus the number of matches.
+pub struct AhoCorasick {
+    trie: Trie,
+    fail: Vec<usize>,
+    // patterns ending at each node, including those ending at the nodes reached by following fail links
+    outputs: Vec<Vec<usize>>,
+}
+
+impl AhoCorasick {
+    pub fn new<P: AsRef<[u8]>>(patterns: &[P]) -> Self {
+        let trie = Trie::new(patterns);
+        let mut fail = vec![0; trie.children.len()];
+        let mut outputs = trie.ends.clone();
+
+        // breadth first, so the fail link of a node's parent is complete before the node itself is processed
+        let mut queue: VecDeque<usize> = trie.children[0].values().copied().collect();
+        while let Some(node) = queue.pop_front() {
+            for (&byte, &child) in &trie.children[node] {
+                let mut candidate = fail[node];
+                while candidate != 0 && !trie.children[candidate].contains_key(&byte) {
+                    candidate = fail[candidate];
+                }
+                fail[child] = trie.children[candidate].get(&byte).copied().filter(|&next| next != child).unwrap_or(0);
+                let inherited = outputs[fail[child]].clone();
+                outputs[child].extend(inherited);
+                queue.push_back(child);
+            }
+        }
+        AhoCorasick { trie, fail, outputs }
+    }
+
+    pub fn pattern_len(&self, pattern: usize) -> usize {
+        self.trie.pattern_len(pattern)
+    }
+
+    /// All occurrences of all patterns, ordered by end position. Empty patterns are not reported.
+    pub fn find_all(&self, text: &[u8]) -> Vec<Match> {
+        let mut matches = Vec::new();
+        let mut node = 0;
+        for (i, byte) in text.iter().enumerate() {
+            loop {
+                if let Some(&next) = self.trie.children[node].get(byte) {
+                    node = next;
+                    break;
+                }
+                if node == 0 {
+                    break;
+                }
+                node = self.fail[node];
+            }
+            for &pattern in &self.outputs[node] {
+                let len = self.trie.pattern_len(pattern);
+                if len > 0 {
+                    matches.push(Match { pattern, start: i + 1 - len, end: i + 1 });
+                }
+            }
+        }
+        matches
+    }
+
+    /// Per byte index of the text, the patterns starting there
+    pub fn matches_by_start(&self, text: &[u8]) -> Vec<Vec<usize>> {
+        let mut by_start = vec![Vec::new(); text.len()];
+        for m in self.find_all(text) {
+            by_start[m.start].push(m.pattern);
+        }
+        by_start
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PATTERNS: [&str; 5] = ["he", "she", "his", "hers", "h"];
+
+    #[test]
+    fn test_trie() {
+        let trie = Trie::new(&PATTERNS);
+        let at = |start| trie.matches_at(b"ushers", start).iter().map(|m| m.pattern).collect::<Vec<_>>();
+        assert_eq!(at(0), Vec::<usize>::new());
+        assert_eq!(at(1), vec![1]);
+        assert_eq!(at(2), vec![4, 0, 3]);
+        assert_eq!(trie.matches_at(b"ushers", 2)[2], Match { pattern: 3, start: 2, end: 6 });
+    }
+
+    #[test]
+    fn test_aho_corasick() {
+        let ac = AhoCorasick::new(&PATTERNS);
+        let found: Vec<_> = ac.find_all(b"ushers").iter().map(|m| (m.pattern, m.start, m.end)).collect();
+        assert_eq!(found, vec![(4, 2, 3), (1, 1, 4), (0, 2, 4), (3, 2, 6)]);
+        assert_eq!(ac.matches_by_start(b"ahishe"), vec![vec![], vec![4, 2], vec![], vec![1], vec![4, 0], vec![]]);
+    }
+
+    #[test]
+    fn test_aho_corasick_against_trie() {
+        let patterns = ["r", "wr", "b", "g", "bwu", "rb", "gb", "br", "a", "aa", "aaa"];
+        let ac = AhoCorasick::new(&patterns);
+        let trie = Trie::new(&patterns);
+        for text in ["brwrr", "bggr", "gbbr", "rrbgbr", "ubwu", "bwurrg", "aaaabrgbwu"] {
+            let by_start = ac.matches_by_start(text.as_bytes());
+            for (start, patterns) in by_start.into_iter().enumerate() {
+                let mut expected: Vec<_> = trie.matches_at(text.as_bytes(), start).iter().map(|m| m.pattern).collect();
+                let mut patterns = patterns;
+                expected.sort();
+                patterns.sort();
+                assert_eq!(patterns, expected, "{} at {}", text, start);
+            }
+        }
+    }
+}
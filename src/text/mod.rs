@@ -0,0 +1,5 @@
+pub mod matcher;
+pub mod scanner;
+
+pub use matcher::{AhoCorasick, Match, Trie};
+pub use scanner::Scanner;
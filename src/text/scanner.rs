@@ -0,0 +1,103 @@
+/// The instructions hidden in corrupted memory
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Instruction {
+    /// `mul(a,b)` with a and b of 1 to 3 digits
+    Mul(u32, u32),
+    /// `do()`
+    Do,
+    /// `don't()`
+    Dont,
+}
+
+/// A well-formed instruction found at byte offset `start`, with the text it was parsed from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Token<'a> {
+    pub instruction: Instruction,
+    pub start: usize,
+    pub text: &'a str,
+}
+
+/// Iterates over the well-formed instructions in a text in one pass, skipping everything else. Tokens borrow from
+/// the input, nothing is copied.
+pub struct Scanner<'a> {
+    input: &'a str,
+    position: usize,
+}
+
+impl <'a> Scanner<'a> {
+    pub fn new(input: &'a str) -> Self {
+        Scanner { input, position: 0 }
+    }
+
+    /// The instruction and its length in bytes, if one starts exactly at `start`
+    fn instruction_at(&self, start: usize) -> Option<(Instruction, usize)> {
+        let rest = &self.input.as_bytes()[start..];
+        if rest.starts_with(b"do()") {
+            return Some((Instruction::Do, 4));
+        }
+        if rest.starts_with(b"don't()") {
+            return Some((Instruction::Dont, 7));
+        }
+        let rest = rest.strip_prefix(b"mul(")?;
+        let (a, a_len) = number(rest)?;
+        let rest = rest[a_len..].strip_prefix(b",")?;
+        let (b, b_len) = number(rest)?;
+        rest[b_len..].strip_prefix(b")")?;
+        Some((Instruction::Mul(a, b), 4 + a_len + 1 + b_len + 1))
+    }
+}
+
+/// A number of 1 to 3 digits at the start of `bytes`, with its length
+fn number(bytes: &[u8]) -> Option<(u32, usize)> {
+    let len = bytes.iter().take(4).take_while(|b| b.is_ascii_digit()).count();
+    if !(1..=3).contains(&len) {
+        return None;
+    }
+    let value = bytes[..len].iter().fold(0, |value, digit| 10 * value + (digit - b'0') as u32);
+    Some((value, len))
+}
+
+impl <'a> Iterator for Scanner<'a> {
+    type Item = Token<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.position < self.input.len() {
+            let start = self.position;
+            match self.instruction_at(start) {
+                Some((instruction, len)) => {
+                    self.position += len;
+                    return Some(Token { instruction, start, text: &self.input[start..start + len] });
+                },
+                None => self.position += 1, // all instructions are ASCII, so byte steps never split one
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scanner() {
+        let tokens: Vec<_> = Scanner::new("xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))").collect();
+        let instructions: Vec<_> = tokens.iter().map(|t| t.instruction).collect();
+        assert_eq!(instructions, [
+            Instruction::Mul(2, 4), Instruction::Dont, Instruction::Mul(5, 5), Instruction::Mul(11, 8),
+            Instruction::Do, Instruction::Mul(8, 5),
+        ]);
+        assert_eq!(tokens[0], Token { instruction: Instruction::Mul(2, 4), start: 1, text: "mul(2,4)" });
+        assert_eq!((tokens[1].start, tokens[1].text), (20, "don't()"));
+    }
+
+    #[test]
+    fn test_malformed() {
+        for text in ["mul(1234,5)", "mul(4,1234)", "mul( 2,3)", "mul(2 ,3)", "mul(,3)", "mul(32,64]", "mul(2,3", "do ()", "dont()", "Mul(2,3)"] {
+            assert_eq!(Scanner::new(text).next(), None, "{}", text);
+        }
+        // overlapping starts and multi-byte characters around instructions
+        let tokens: Vec<_> = Scanner::new("mumul(1,2)ümul(999,0)do()don't()").map(|t| (t.instruction, t.start)).collect();
+        assert_eq!(tokens, [(Instruction::Mul(1, 2), 2), (Instruction::Mul(999, 0), 12), (Instruction::Do, 22), (Instruction::Dont, 26)]);
+    }
+}
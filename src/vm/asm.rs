@@ -0,0 +1,160 @@
+use std::fmt;
+
+use super::Error;
+
+/// The eight instructions of the three-bit computer, in opcode order
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Opcode {
+    Adv,
+    Bxl,
+    Bst,
+    Jnz,
+    Bxc,
+    Out,
+    Bdv,
+    Cdv,
+}
+
+const MNEMONICS: [&str; 8] = ["adv", "bxl", "bst", "jnz", "bxc", "out", "bdv", "cdv"];
+
+impl Opcode {
+    pub fn from_u8(opcode: u8) -> Option<Self> {
+        use Opcode::*;
+        [Adv, Bxl, Bst, Jnz, Bxc, Out, Bdv, Cdv].get(opcode as usize).copied()
+    }
+
+    pub fn mnemonic(&self) -> &'static str {
+        MNEMONICS[*self as usize]
+    }
+
+    /// Whether the operand is a combo operand (0-3 literal, 4-6 register A-C) instead of a literal
+    pub fn has_combo_operand(&self) -> bool {
+        matches!(self, Opcode::Adv | Opcode::Bst | Opcode::Out | Opcode::Bdv | Opcode::Cdv)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Instruction {
+    pub opcode: Opcode,
+    pub operand: u8,
+}
+
+impl Instruction {
+    /// The operand as written in assembly: registers by name for combo operands, numbers otherwise
+    fn operand_str(&self) -> String {
+        match (self.opcode.has_combo_operand(), self.operand) {
+            (true, 4) => String::from("A"),
+            (true, 5) => String::from("B"),
+            (true, 6) => String::from("C"),
+            (_, operand) => operand.to_string(),
+        }
+    }
+
+    /// What the instruction does, e.g. `C = A >> B` for `cdv B`
+    pub fn describe(&self) -> String {
+        let combo = self.operand_str();
+        match self.opcode {
+            Opcode::Adv => format!("A = A >> {}", combo),
+            Opcode::Bxl => format!("B = B ^ {}", self.operand),
+            Opcode::Bst => format!("B = {} % 8", combo),
+            Opcode::Jnz => format!("if A != 0 jump to {}", self.operand),
+            Opcode::Bxc => String::from("B = B ^ C"),
+            Opcode::Out => format!("output {} % 8", combo),
+            Opcode::Bdv => format!("B = A >> {}", combo),
+            Opcode::Cdv => format!("C = A >> {}", combo),
+        }
+    }
+}
+
+/// Mnemonic form, e.g. `bst A`, `bxl 5` or `jnz 0`. The ignored operand of `bxc` is only written when it is not 0,
+/// so that assembling the text gives back the same program.
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.opcode, self.operand) {
+            (Opcode::Bxc, 0) => write!(f, "bxc"),
+            _ => write!(f, "{} {}", self.opcode.mnemonic(), self.operand_str()),
+        }
+    }
+}
+
+/// The instructions of a program, a trailing opcode without operand is left out
+pub fn disassemble(program: &[u8]) -> Result<Vec<Instruction>, Error> {
+    program.chunks_exact(2).enumerate()
+    .map(|(i, pair)| Ok(Instruction {
+        opcode: Opcode::from_u8(pair[0]).ok_or(Error::InvalidOpcode(2 * i))?,
+        operand: pair[1],
+    }))
+    .collect()
+}
+
+/// One instruction per line, prefixed by its pointer and followed by a description:
+/// ```text
+///  0: bst A     ; B = A % 8
+///  2: bxl 5     ; B = B ^ 5
+/// ```
+pub fn pretty_print(program: &[u8]) -> Result<String, Error> {
+    Ok(disassemble(program)?.iter().enumerate()
+    .map(|(i, instruction)| format!("{:2}: {:<9} ; {}\n", 2 * i, instruction.to_string(), instruction.describe()))
+    .collect())
+}
+
+/// Turns one instruction per line back into a program. Comments after `;`, pointer prefixes like ` 2:` and empty
+/// lines are ignored, so the output of `pretty_print` can be assembled again.
+pub fn assemble(source: &str) -> Result<Vec<u8>, Error> {
+    let mut program = Vec::new();
+    for (number, line) in source.lines().enumerate() {
+        let code = line.split(';').next().unwrap_or_default();
+        let code = code.split_once(':').map_or(code, |(_, instruction)| instruction).trim();
+        if code.is_empty() {
+            continue;
+        }
+        let error = |reason: &str| Error::Parse(format!("line {}: {} in '{}'", number + 1, reason, code));
+        let mut words = code.split_whitespace();
+        let mnemonic = words.next().unwrap_or_default().to_lowercase();
+        let opcode = MNEMONICS.iter().position(|m| *m == mnemonic)
+            .and_then(|opcode| Opcode::from_u8(opcode as u8))
+            .ok_or_else(|| error("unknown instruction"))?;
+        let operand = match (words.next(), opcode) {
+            (None, Opcode::Bxc) => 0,
+            (None, _) => return Err(error("missing operand")),
+            (Some("A"), o) if o.has_combo_operand() => 4,
+            (Some("B"), o) if o.has_combo_operand() => 5,
+            (Some("C"), o) if o.has_combo_operand() => 6,
+            (Some(n), _) => match n.parse() {
+                Ok(n) if n < 8 => n,
+                _ => return Err(error("operand is not a 3-bit number")),
+            },
+        };
+        if words.next().is_some() {
+            return Err(error("too many operands"));
+        }
+        program.push(opcode as u8);
+        program.push(operand);
+    }
+    Ok(program)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PROGRAM: [u8; 16] = [2, 4, 1, 5, 7, 5, 1, 6, 0, 3, 4, 2, 5, 5, 3, 0];
+
+    #[test]
+    fn test_disassemble() {
+        let mnemonics: Vec<String> = disassemble(&PROGRAM).unwrap().iter().map(Instruction::to_string).collect();
+        assert_eq!(mnemonics, ["bst A", "bxl 5", "cdv B", "bxl 6", "adv 3", "bxc 2", "out B", "jnz 0"]);
+        assert_eq!(pretty_print(&PROGRAM[..6]).unwrap(), " 0: bst A     ; B = A % 8\n 2: bxl 5     ; B = B ^ 5\n 4: cdv B     ; C = A >> B\n");
+        assert_eq!(disassemble(&[1, 0, 9, 0]), Err(Error::InvalidOpcode(2)));
+        assert_eq!(pretty_print(&[8, 0]), Err(Error::InvalidOpcode(0)));
+    }
+
+    #[test]
+    fn test_assemble() {
+        assert_eq!(assemble(&pretty_print(&PROGRAM).unwrap()), Ok(PROGRAM.to_vec()));
+        assert_eq!(assemble("adv 1\n\nout A ; print\nbxc\njnz 0"), Ok(vec![0, 1, 5, 4, 4, 0, 3, 0]));
+        assert_eq!(assemble("bxl A"), Err(Error::Parse(String::from("line 1: operand is not a 3-bit number in 'bxl A'"))));
+        assert!(assemble("mul 3").is_err());
+        assert!(assemble("out").is_err());
+    }
+}
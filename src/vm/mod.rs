@@ -0,0 +1,188 @@
+pub mod asm;
+pub mod quine;
+pub mod trace;
+
+use std::fmt;
+
+/// The three-bit computer: three registers, a program of 3-bit numbers and an instruction pointer.
+/// Instructions are an opcode followed by an operand, the machine halts when the pointer is past the program.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Machine {
+    pub a: u64,
+    pub b: u64,
+    pub c: u64,
+    pub program: Vec<u8>,
+    pub pointer: usize,
+    pub output: Vec<u8>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// The puzzle text could not be parsed, with a description of the offending part
+    Parse(String),
+    /// Combo operand 7 is reserved, with the pointer of the instruction using it
+    InvalidComboOperand(usize),
+    /// An opcode that is not a 3-bit number, with its pointer
+    InvalidOpcode(usize),
+    /// The machine did not halt within this number of steps
+    StepLimit(usize),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Parse(reason) => write!(f, "Cannot parse machine: {}", reason),
+            Error::InvalidComboOperand(pointer) => write!(f, "Invalid combo operand 7 at pointer {}", pointer),
+            Error::InvalidOpcode(pointer) => write!(f, "Invalid opcode at pointer {}", pointer),
+            Error::StepLimit(steps) => write!(f, "Machine did not halt within {} steps", steps),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl Machine {
+    pub fn new(a: u64, b: u64, c: u64, program: Vec<u8>) -> Self {
+        Machine { a, b, c, program, pointer: 0, output: Vec::new() }
+    }
+
+    /// Parses the puzzle text format:
+    /// ```text
+    /// Register A: 729
+    /// Register B: 0
+    /// Register C: 0
+    ///
+    /// Program: 0,1,5,4,3,0
+    /// ```
+    pub fn parse(input: &str) -> Result<Self, Error> {
+        let mut lines = input.lines().map(str::trim).filter(|line| !line.is_empty());
+        let mut register = |name: &str| -> Result<u64, Error> {
+            let line = lines.next().ok_or_else(|| Error::Parse(format!("missing register {}", name)))?;
+            line.strip_prefix(&format!("Register {}: ", name))
+                .and_then(|value| value.parse().ok())
+                .ok_or_else(|| Error::Parse(format!("expected register {}, got '{}'", name, line)))
+        };
+        let (a, b, c) = (register("A")?, register("B")?, register("C")?);
+
+        let line = lines.next().ok_or_else(|| Error::Parse(String::from("missing program")))?;
+        let program = line.strip_prefix("Program: ")
+            .ok_or_else(|| Error::Parse(format!("expected program, got '{}'", line)))?
+            .split(',')
+            .map(|n| match n.trim().parse() {
+                Ok(n) if n < 8 => Ok(n),
+                _ => Err(Error::Parse(format!("'{}' is not a 3-bit number", n))),
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Machine::new(a, b, c, program))
+    }
+
+    pub fn is_halted(&self) -> bool {
+        self.pointer + 1 >= self.program.len()
+    }
+
+    /// Executes the instruction at the pointer, returns false without doing anything if the machine halted
+    pub fn step(&mut self) -> Result<bool, Error> {
+        if self.is_halted() {
+            return Ok(false);
+        }
+        let operand = self.program[self.pointer + 1];
+        match self.program[self.pointer] {
+            0 => self.a = self.divide(operand)?,
+            1 => self.b ^= operand as u64,
+            2 => self.b = self.combo(operand)? % 8,
+            3 => if self.a != 0 {
+                self.pointer = operand as usize;
+                return Ok(true);
+            },
+            4 => self.b ^= self.c,
+            5 => self.output.push((self.combo(operand)? % 8) as u8),
+            6 => self.b = self.divide(operand)?,
+            7 => self.c = self.divide(operand)?,
+            _ => return Err(Error::InvalidOpcode(self.pointer)),
+        }
+        self.pointer += 2;
+        Ok(true)
+    }
+
+    /// Runs until the machine halts and returns its output
+    pub fn run(&mut self) -> Result<&[u8], Error> {
+        while self.step()? {}
+        Ok(&self.output)
+    }
+
+    /// The output in the puzzle answer format, e.g. `4,6,3,5`
+    pub fn output_string(&self) -> String {
+        self.output.iter().map(u8::to_string).collect::<Vec<_>>().join(",")
+    }
+
+    fn combo(&self, operand: u8) -> Result<u64, Error> {
+        match operand {
+            0..=3 => Ok(operand as u64),
+            4 => Ok(self.a),
+            5 => Ok(self.b),
+            6 => Ok(self.c),
+            _ => Err(Error::InvalidComboOperand(self.pointer)),
+        }
+    }
+
+    // A divided by 2 to the power of the combo operand, which is a right shift (that would overflow for large operands)
+    fn divide(&self, operand: u8) -> Result<u64, Error> {
+        let shift = self.combo(operand)?;
+        Ok(if shift >= 64 { 0 } else { self.a >> shift })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_instructions() {
+        let mut m = Machine::new(0, 0, 9, vec![2, 6]);
+        m.run().unwrap();
+        assert_eq!(m.b, 1);
+
+        let mut m = Machine::new(10, 0, 0, vec![5, 0, 5, 1, 5, 4]);
+        assert_eq!(m.run().unwrap(), &[0, 1, 2]);
+
+        let mut m = Machine::new(2024, 0, 0, vec![0, 1, 5, 4, 3, 0]);
+        m.run().unwrap();
+        assert_eq!((m.a, m.output_string().as_str()), (0, "4,2,5,6,7,7,7,7,3,1,0"));
+
+        let mut m = Machine::new(0, 2024, 43690, vec![4, 0]);
+        m.run().unwrap();
+        assert_eq!(m.b, 44354);
+
+        let mut m = Machine::new(0, 0, 0, vec![5, 7]);
+        assert_eq!(m.run(), Err(Error::InvalidComboOperand(0)));
+
+        let mut m = Machine::new(0, 0, 0, vec![1, 0, 8, 0]);
+        assert_eq!(m.run(), Err(Error::InvalidOpcode(2)));
+    }
+
+    #[test]
+    fn test_parse_and_run() {
+        let mut m = Machine::parse("
+            Register A: 729
+            Register B: 0
+            Register C: 0
+
+            Program: 0,1,5,4,3,0
+        ").unwrap();
+        assert_eq!((m.a, &m.program), (729, &vec![0, 1, 5, 4, 3, 0]));
+        m.run().unwrap();
+        assert_eq!(m.output_string(), "4,6,3,5,6,3,5,2,1,0");
+
+        assert_eq!(Machine::parse("Register A: 1\nRegister B: 0\nRegister C: 0\nProgram: 0,8"),
+            Err(Error::Parse(String::from("'8' is not a 3-bit number"))));
+        assert!(Machine::parse("Register A: 1\nRegister C: 0").is_err());
+    }
+
+    #[test]
+    fn test_large_registers() {
+        // part B answers need more than 32 bits
+        let mut m = Machine::new(1 << 50, 0, 0, vec![0, 3, 5, 4, 3, 0]);
+        m.run().unwrap();
+        assert_eq!(m.output.len(), 17);
+    }
+}
@@ -0,0 +1,89 @@
+use super::{Error, Machine};
+
+/// Step limit per candidate run: a candidate that does not halt cannot be a quine
+const MAX_STEPS: usize = 100_000;
+
+/// Finds the lowest value for register A that makes the machine output its own program.
+///
+/// Puzzle programs loop over A, outputting a value derived from its lowest three bits and shifting it three bits
+/// to the right until it is 0. So the last output only depends on the highest three bits, the one before that on
+/// the highest six, etc. That allows building A backward three bits at a time, keeping only the candidates whose
+/// output matches the end of the program. If no candidate is found that way (the program does not have that
+/// structure), all values up to `brute_force_limit` are tried.
+pub fn find_quine(machine: &Machine, brute_force_limit: u64) -> Result<Option<u64>, Error> {
+    match search_backward(machine, 0, machine.program.len())? {
+        Some(a) => Ok(Some(a)),
+        None => find_quine_brute_force(machine, brute_force_limit),
+    }
+}
+
+/// Tries every value from 0 up to and including `limit` for register A
+pub fn find_quine_brute_force(machine: &Machine, limit: u64) -> Result<Option<u64>, Error> {
+    for a in 0..=limit {
+        if output_for(machine, a)?.is_some_and(|output| output == machine.program) {
+            return Ok(Some(a));
+        }
+    }
+    Ok(None)
+}
+
+// Depth first with ascending digits, so the first complete match is the lowest
+fn search_backward(machine: &Machine, prefix: u64, matched_from: usize) -> Result<Option<u64>, Error> {
+    if matched_from == 0 {
+        return Ok(Some(prefix));
+    }
+    if prefix > u64::MAX >> 3 {
+        return Ok(None);
+    }
+    for digit in 0..8 {
+        let a = prefix << 3 | digit;
+        if a == 0 && matched_from < machine.program.len() {
+            continue; // shifting 0 never adds digits, so the search would not progress
+        }
+        let matches = output_for(machine, a)?.is_some_and(|output| output == machine.program[matched_from - 1..]);
+        if matches {
+            if let Some(found) = search_backward(machine, a, matched_from - 1)? {
+                return Ok(Some(found));
+            }
+        }
+    }
+    Ok(None)
+}
+
+/// The output of the machine when starting with `a` in register A, None if it did not halt
+fn output_for(machine: &Machine, a: u64) -> Result<Option<Vec<u8>>, Error> {
+    let mut candidate = Machine::new(a, machine.b, machine.c, machine.program.clone());
+    match candidate.run_limited(MAX_STEPS) {
+        Ok(output) => Ok(Some(output.to_vec())),
+        Err(Error::StepLimit(_)) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_quine() {
+        let machine = Machine::new(2024, 0, 0, vec![0, 3, 5, 4, 3, 0]);
+        assert_eq!(find_quine(&machine, 0), Ok(Some(117440)));
+        assert_eq!(find_quine_brute_force(&machine, 117440), Ok(Some(117440)));
+        assert_eq!(find_quine_brute_force(&machine, 117439), Ok(None));
+    }
+
+    #[test]
+    fn test_find_quine_puzzle_program() {
+        let program = vec![2, 4, 1, 5, 7, 5, 1, 6, 0, 3, 4, 2, 5, 5, 3, 0];
+        let a = find_quine(&Machine::new(0, 0, 0, program.clone()), 0).unwrap().unwrap();
+        let mut machine = Machine::new(a, 0, 0, program.clone());
+        assert_eq!(machine.run().unwrap(), program.as_slice());
+    }
+
+    #[test]
+    fn test_no_quine() {
+        // outputs A % 8 once, which can never be the two values of the program itself
+        let machine = Machine::new(0, 0, 0, vec![5, 4]);
+        assert_eq!(find_quine(&machine, 1000), Ok(None));
+    }
+}
@@ -0,0 +1,103 @@
+use std::fmt;
+
+use super::asm::{Instruction, Opcode};
+use super::{Error, Machine};
+
+/// The machine state just before an instruction is executed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TraceEntry {
+    pub step: usize,
+    pub pointer: usize,
+    pub instruction: Instruction,
+    pub a: u64,
+    pub b: u64,
+    pub c: u64,
+}
+
+/// One line per entry, e.g. `   3  6: out A     A=364 B=0 C=0`
+impl fmt::Display for TraceEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:4} {:2}: {:<9} A={} B={} C={}", self.step, self.pointer, self.instruction.to_string(), self.a, self.b, self.c)
+    }
+}
+
+/// Records every entry passed to `record`, to use as hook for `Machine::run_traced`
+#[derive(Debug, Default)]
+pub struct Trace(pub Vec<TraceEntry>);
+
+impl Trace {
+    pub fn record(&mut self, entry: &TraceEntry) {
+        self.0.push(*entry);
+    }
+}
+
+/// The dump of all entries, one per line
+impl fmt::Display for Trace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.iter().try_for_each(|entry| writeln!(f, "{}", entry))
+    }
+}
+
+impl Machine {
+    /// Like `run`, but gives `Error::StepLimit` instead of looping forever if the machine did not halt after
+    /// `max_steps` instructions
+    pub fn run_limited(&mut self, max_steps: usize) -> Result<&[u8], Error> {
+        self.run_traced(max_steps, |_| {})
+    }
+
+    /// Like `run_limited`, calling `on_step` with the state before every instruction
+    pub fn run_traced<F>(&mut self, max_steps: usize, mut on_step: F) -> Result<&[u8], Error> where
+        F: FnMut(&TraceEntry),
+    {
+        for step in 0..max_steps {
+            if self.is_halted() {
+                return Ok(&self.output);
+            }
+            on_step(&TraceEntry {
+                step,
+                pointer: self.pointer,
+                instruction: Instruction {
+                    opcode: Opcode::from_u8(self.program[self.pointer]).ok_or(Error::InvalidOpcode(self.pointer))?,
+                    operand: self.program[self.pointer + 1],
+                },
+                a: self.a,
+                b: self.b,
+                c: self.c,
+            });
+            self.step()?;
+        }
+        if self.is_halted() { Ok(&self.output) } else { Err(Error::StepLimit(max_steps)) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trace() {
+        let mut m = Machine::new(729, 0, 0, vec![0, 1, 5, 4, 3, 0]);
+        let mut trace = Trace::default();
+        m.run_traced(100, |entry| trace.record(entry)).unwrap();
+        assert_eq!(trace.0.len(), 30);
+        assert_eq!(trace.to_string().lines().take(4).collect::<Vec<_>>(), [
+            "   0  0: adv 1     A=729 B=0 C=0",
+            "   1  2: out A     A=364 B=0 C=0",
+            "   2  4: jnz 0     A=364 B=0 C=0",
+            "   3  0: adv 1     A=364 B=0 C=0",
+        ]);
+    }
+
+    #[test]
+    fn test_step_limit() {
+        // jumps to itself forever
+        let mut m = Machine::new(1, 0, 0, vec![3, 0]);
+        assert_eq!(m.run_limited(1000), Err(Error::StepLimit(1000)));
+
+        let mut m = Machine::new(0, 0, 0, vec![1, 0, 9, 0]);
+        assert_eq!(m.run_traced(10, |_| {}), Err(Error::InvalidOpcode(2)));
+
+        let mut m = Machine::new(10, 0, 0, vec![5, 0, 5, 1, 5, 4]);
+        assert_eq!(m.run_limited(3), Ok(&[0, 1, 2][..]));
+    }
+}
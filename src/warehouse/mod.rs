@@ -0,0 +1,222 @@
+use std::collections::HashSet;
+use std::fmt;
+
+use crate::map::{read_map, Mapp};
+
+/// A warehouse with walls (`#`), a robot (`@`) and boxes that are either a single cell (`O`) or two cells wide
+/// (`[]`). The robot pushes any boxes in its way, and the boxes push the boxes in their way, unless one of them
+/// would be pushed into a wall, in which case nothing moves.
+pub struct Warehouse {
+    map: Mapp<char>,
+    robot: (usize, usize),
+}
+
+/// Splits the puzzle input in the map and the moves, which may be spread over several lines
+pub fn parse(lines: impl Iterator<Item = String>) -> (Mapp<char>, String) {
+    let (map, moves): (Vec<String>, Vec<String>) = lines
+        .filter(|line| !line.is_empty())
+        .partition(|line| line.starts_with('#'));
+    (read_map(map), moves.concat())
+}
+
+/// The map with every cell twice as wide, where a box becomes a wide box and the robot stays a single cell
+pub fn widen(map: &Mapp<char>) -> Mapp<char> {
+    let rows = (0..map.height()).map(|y| (0..map.width()).map(|x| match map.get(x, y) {
+        Some('O') => "[]",
+        Some('@') => "@.",
+        Some('#') => "##",
+        _ => "..",
+    }).collect()).collect();
+    read_map(rows)
+}
+
+fn offset(direction: char) -> (isize, isize) {
+    match direction {
+        '^' => (0, -1),
+        '>' => (1, 0),
+        'v' => (0, 1),
+        '<' => (-1, 0),
+        _ => panic!("Unknown move {}", direction),
+    }
+}
+
+impl Warehouse {
+    /// None if there is no robot on the map
+    pub fn new(map: Mapp<char>) -> Option<Self> {
+        let robot = map.find(|&c| c == '@')?;
+        Some(Warehouse { map, robot })
+    }
+
+    pub fn map(&self) -> &Mapp<char> {
+        &self.map
+    }
+
+    pub fn robot(&self) -> (usize, usize) {
+        self.robot
+    }
+
+    /// The cells that move when the robot moves in `direction`: the robot and every box pushed, directly or through
+    /// other boxes. None if something would be pushed into a wall.
+    fn pushed(&self, direction: char) -> Option<Vec<(usize, usize)>> {
+        let (dx, dy) = offset(direction);
+        let mut pushed = vec![self.robot];
+        let mut seen = HashSet::from([self.robot]);
+        let mut i = 0;
+        while i < pushed.len() {
+            let (x, y) = pushed[i];
+            i += 1;
+            let ahead = (x.checked_add_signed(dx)?, y.checked_add_signed(dy)?);
+            let other_half = match self.map.get(ahead.0, ahead.1)? {
+                '.' => continue,
+                'O' => None,
+                '[' => Some((ahead.0 + 1, ahead.1)),
+                ']' => Some((ahead.0 - 1, ahead.1)),
+                _ => return None,
+            };
+            for cell in std::iter::once(ahead).chain(other_half) {
+                if seen.insert(cell) {
+                    pushed.push(cell);
+                }
+            }
+        }
+        Some(pushed)
+    }
+
+    /// Moves the robot one cell, pushing boxes as needed. Returns whether the robot moved.
+    pub fn step(&mut self, direction: char) -> bool {
+        let Some(pushed) = self.pushed(direction) else { return false };
+        let (dx, dy) = offset(direction);
+        let contents: Vec<char> = pushed.iter().map(|&(x, y)| std::mem::replace(self.map.get_mut(x, y).unwrap(), '.')).collect();
+        for (&(x, y), c) in pushed.iter().zip(contents) {
+            *self.map.get_mut(x.wrapping_add_signed(dx), y.wrapping_add_signed(dy)).unwrap() = c;
+        }
+        self.robot = (self.robot.0.wrapping_add_signed(dx), self.robot.1.wrapping_add_signed(dy));
+        true
+    }
+
+    /// Performs all moves, ignoring anything that is not a move such as line breaks
+    pub fn run(&mut self, moves: &str) {
+        for direction in moves.chars().filter(|c| "^>v<".contains(*c)) {
+            self.step(direction);
+        }
+    }
+
+    /// The sum over all boxes of 100 times the distance from the top plus the distance from the left of the box,
+    /// where wide boxes count from their left half
+    pub fn gps(&self) -> usize {
+        (0..self.map.height())
+            .flat_map(|y| (0..self.map.width()).map(move |x| (x, y)))
+            .filter(|&(x, y)| matches!(self.map.get(x, y), Some('O') | Some('[')))
+            .map(|(x, y)| 100 * y + x)
+            .sum()
+    }
+}
+
+impl fmt::Display for Warehouse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.map)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{io_lines_from, map_from};
+
+    fn lines(input: &str) -> impl Iterator<Item = String> + '_ {
+        io_lines_from(input).map(Result::unwrap)
+    }
+
+    fn rendered(input: &str) -> String {
+        lines(input).map(|line| line + "\n").collect()
+    }
+
+    #[test]
+    fn test_small_boxes() {
+        let (map, moves) = parse(lines("
+            ########
+            #..O.O.#
+            ##@.O..#
+            #...O..#
+            #.#.O..#
+            #...O..#
+            #......#
+            ########
+
+            <^^>>>vv<v>>v<<
+        "));
+        let mut warehouse = Warehouse::new(map).unwrap();
+        assert!(!warehouse.step('<'));
+        assert!(warehouse.step('^'));
+        warehouse.run(&moves[2..]);
+        assert_eq!(warehouse.to_string(), rendered("
+            ########
+            #....OO#
+            ##.....#
+            #.....O#
+            #.#O@..#
+            #...O..#
+            #...O..#
+            ########
+        "));
+        assert_eq!(warehouse.gps(), 2028);
+    }
+
+    #[test]
+    fn test_wide_boxes() {
+        let (map, moves) = parse(lines("
+            #######
+            #...#.#
+            #.....#
+            #..OO@#
+            #..O..#
+            #.....#
+            #######
+
+            <vv<<^^<<^^
+        "));
+        let mut warehouse = Warehouse::new(widen(&map)).unwrap();
+        assert_eq!(warehouse.robot(), (10, 3));
+        warehouse.run(&moves);
+        assert_eq!(warehouse.to_string(), rendered("
+            ##############
+            ##...[].##..##
+            ##...@.[]...##
+            ##....[]....##
+            ##..........##
+            ##..........##
+            ##############
+        "));
+        assert_eq!(warehouse.gps(), 105 + 207 + 306);
+    }
+
+    #[test]
+    fn test_blocked_cascade() {
+        let warehouse = |input: &str| Warehouse::new(map_from(input)).unwrap();
+        // the box pushed up pushes the two boxes above it, and the right one of those is under a wall
+        let blocked = "
+            #######
+            #....##
+            #.[][]#
+            #..[].#
+            #..@..#
+            #######
+        ";
+        let mut stuck = warehouse(blocked);
+        assert!(!stuck.step('^'));
+        assert_eq!(stuck.robot(), (3, 4));
+        assert_eq!(stuck.to_string(), rendered(blocked));
+
+        // without the wall all three boxes move
+        let mut free = warehouse(&blocked.replace("#....##", "#.....#"));
+        assert!(free.step('^'));
+        assert_eq!(free.to_string(), rendered("
+            #######
+            #.[][]#
+            #..[].#
+            #..@..#
+            #.....#
+            #######
+        "));
+    }
+}